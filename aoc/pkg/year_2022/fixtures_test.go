@@ -0,0 +1,56 @@
+package year_2022
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"aoc/pkg/structs"
+)
+
+// The fixtures under testdata are shared with the Rust crates in experiment/,
+// so both implementations are held to the same answers.
+func readFixture(t *testing.T, day int) ([]structs.Group, []string) {
+	dir := filepath.Join("testdata", fmt.Sprintf("day-%02d", day))
+
+	input, err := os.ReadFile(filepath.Join(dir, "input.txt"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	answers, err := os.ReadFile(filepath.Join(dir, "answers.txt"))
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	contentsByGroup := strings.Split(string(input), "\n\n")
+	groups := make([]structs.Group, len(contentsByGroup))
+	for idx, content := range contentsByGroup {
+		groups[idx] = structs.Group{
+			Contents: content,
+		}
+	}
+	return groups, strings.Split(strings.TrimSpace(string(answers)), "\n")
+}
+
+func TestFixtures(t *testing.T) {
+	solutions := map[int]Solution{
+		1: &Day01{},
+		2: &Day02{},
+		3: &Day03{},
+		4: &Day04{},
+		5: &Day05{},
+	}
+
+	for day, solution := range solutions {
+		inputs, answers := readFixture(t, day)
+
+		if answer := solution.Part1(inputs); answer != answers[0] {
+			t.Errorf("Day %d Part 1: got %s, want %s", day, answer, answers[0])
+		}
+		if answer := solution.Part2(inputs); answer != answers[1] {
+			t.Errorf("Day %d Part 2: got %s, want %s", day, answer, answers[1])
+		}
+	}
+}
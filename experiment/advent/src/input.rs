@@ -0,0 +1,225 @@
+use std::fmt::Display;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+
+/// Where the puzzle input ended up being read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    Path(PathBuf),
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::Path(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+
+pub enum InputError {
+    /// No path was given, the environment variable isn't set
+    /// and there's nothing in the cache for that day.
+    Missing { year: u16, day: u8 },
+    /// We knew where to look but couldn't read from there.
+    Unreadable { source: InputSource, error: std::io::Error },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Missing { year, day } => write!(
+                f,
+                "No input found for day {} ({}). Pass a path (or `-` for stdin), set {} or put it in the cache.",
+                day, year, INPUT_ENV_VAR
+            ),
+            InputError::Unreadable { source, error } => write!(f, "Could not read input from {}: {}", source, error),
+        }
+    }
+}
+
+// Binaries return `Result<(), InputError>` from `main`, which prints the `Debug` repr,
+// so keep that readable.
+impl std::fmt::Debug for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Missing { .. } => None,
+            InputError::Unreadable { error, .. } => Some(error),
+        }
+    }
+}
+
+
+/// Path to the input file, used when none is passed explicitly.
+pub const INPUT_ENV_VAR: &str = "AOC_INPUT";
+
+/// Overrides the root of the input cache.
+pub const CACHE_DIR_ENV_VAR: &str = "AOC_CACHE_DIR";
+
+
+/// The default root of the input cache:
+/// `$AOC_CACHE_DIR`, `$XDG_CACHE_HOME/aoc` or `~/.cache/aoc`, whichever is set first.
+pub fn default_cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os(CACHE_DIR_ENV_VAR) {
+        return Some(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("XDG_CACHE_HOME") {
+        return Some(PathBuf::from(dir).join("aoc"));
+    }
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache").join("aoc"))
+}
+
+
+/// Where everything we keep about a day lives in the cache rooted at `cache_dir`.
+pub fn cache_day_dir(cache_dir: &Path, year: u16, day: u8) -> PathBuf {
+    cache_dir.join(year.to_string()).join(format!("day-{:02}", day))
+}
+
+pub fn cached_input_path(cache_dir: &Path, year: u16, day: u8) -> PathBuf {
+    cache_day_dir(cache_dir, year, day).join("input.txt")
+}
+
+
+/// Resolves a day's puzzle input at runtime, trying in order:
+///
+/// 1. `path`, where `-` means stdin,
+/// 2. the file named by the `env_var` environment variable,
+/// 3. `<cache_dir>/<year>/day-<day>/input.txt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputLoader {
+    pub year: u16,
+    pub day: u8,
+    pub path: Option<String>,
+    pub env_var: String,
+    pub cache_dir: Option<PathBuf>,
+}
+
+impl InputLoader {
+    pub fn new(day: u8) -> Self {
+        Self {
+            year: 2022,
+            day,
+            path: None,
+            env_var: INPUT_ENV_VAR.to_string(),
+            cache_dir: default_cache_dir(),
+        }
+    }
+
+    /// A loader for the day's binary, which takes the input path as its first argument.
+    pub fn from_args(day: u8) -> Self {
+        let mut loader = Self::new(day);
+        loader.path = std::env::args().nth(1);
+        loader
+    }
+
+    pub fn cache_day_dir(&self) -> Option<PathBuf> {
+        self.cache_dir
+            .as_ref()
+            .map(|dir| cache_day_dir(dir, self.year, self.day))
+    }
+
+    pub fn cached_input_path(&self) -> Option<PathBuf> {
+        self.cache_dir
+            .as_ref()
+            .map(|dir| cached_input_path(dir, self.year, self.day))
+    }
+
+    /// Figure out where the input would be read from, without reading it.
+    pub fn resolve(&self) -> Option<InputSource> {
+        if let Some(path) = &self.path {
+            return match path.as_str() {
+                "-" => Some(InputSource::Stdin),
+                path => Some(InputSource::Path(PathBuf::from(path))),
+            };
+        }
+
+        if let Some(path) = std::env::var_os(&self.env_var) {
+            return Some(InputSource::Path(PathBuf::from(path)));
+        }
+
+        self.cached_input_path()
+            .filter(|path| path.is_file())
+            .map(InputSource::Path)
+    }
+
+    pub fn load(&self) -> Result<String, InputError> {
+        let source = self.resolve().ok_or(InputError::Missing { year: self.year, day: self.day })?;
+
+        let contents = match &source {
+            InputSource::Stdin => {
+                let mut buffer = String::new();
+                std::io::stdin().read_to_string(&mut buffer).map(|_| buffer)
+            },
+            InputSource::Path(path) => std::fs::read_to_string(path),
+        };
+
+        contents.map_err(|error| InputError::Unreadable { source, error })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh loader that doesn't see the real environment or cache.
+    fn loader(name: &str) -> (InputLoader, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("advent-input-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut loader = InputLoader::new(6);
+        loader.env_var = format!("ADVENT_TEST_INPUT_{}", name.to_uppercase());
+        loader.cache_dir = Some(dir.join("cache"));
+        (loader, dir)
+    }
+
+    #[test]
+    fn missing_input_is_an_error() {
+        let (loader, _) = loader("missing");
+        assert!(matches!(loader.load(), Err(InputError::Missing { year: 2022, day: 6 })));
+    }
+
+    #[test]
+    fn explicit_path_wins() {
+        let (mut loader, dir) = loader("explicit");
+        std::fs::write(dir.join("explicit.txt"), "explicit").unwrap();
+        std::fs::create_dir_all(loader.cache_day_dir().unwrap()).unwrap();
+        std::fs::write(loader.cached_input_path().unwrap(), "cached").unwrap();
+
+        loader.path = Some(dir.join("explicit.txt").to_string_lossy().to_string());
+        assert_eq!(loader.load().unwrap(), "explicit");
+
+        loader.path = Some("-".to_string());
+        assert_eq!(loader.resolve(), Some(InputSource::Stdin));
+    }
+
+    #[test]
+    fn env_var_before_cache() {
+        let (loader, dir) = loader("env");
+        std::fs::write(dir.join("env.txt"), "from env").unwrap();
+        std::fs::create_dir_all(loader.cache_day_dir().unwrap()).unwrap();
+        std::fs::write(loader.cached_input_path().unwrap(), "cached").unwrap();
+
+        assert_eq!(loader.load().unwrap(), "cached");
+        std::env::set_var(&loader.env_var, dir.join("env.txt"));
+        assert_eq!(loader.load().unwrap(), "from env");
+        std::env::remove_var(&loader.env_var);
+    }
+
+    #[test]
+    fn unreadable_path_is_an_error() {
+        let (mut loader, dir) = loader("unreadable");
+        loader.path = Some(dir.join("nope.txt").to_string_lossy().to_string());
+        assert!(matches!(loader.load(), Err(InputError::Unreadable { .. })));
+    }
+}
@@ -0,0 +1,13 @@
+mod input;
+mod provider;
+#[cfg(any(test, feature = "testing"))]
+pub mod rng;
+mod solution;
+#[cfg(test)]
+mod stand_in;
+mod submit;
+
+pub use input::*;
+pub use provider::*;
+pub use solution::*;
+pub use submit::*;
@@ -0,0 +1,180 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use crate::{cached_input_path, default_cache_dir};
+
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Overrides [`DEFAULT_BASE_URL`], e.g. to point at a mirror or a stand-in server.
+pub const BASE_URL_ENV_VAR: &str = "AOC_BASE_URL";
+
+/// The `session` cookie from a logged in browser. Same one the Go side uses.
+pub const SESSION_ENV_VAR: &str = "AOC_SESSION_ID";
+
+/// Advent of Code asks automated tools to identify themselves.
+pub const USER_AGENT: &str = "github.com/aalekhpatel07/advent-of-code-2022";
+
+
+#[derive(Debug)]
+pub enum ProviderError {
+    /// Not in the cache, and we can't fetch it without a session.
+    MissingSession,
+    /// The server answered, but not with the input.
+    Status { url: String, status: u16, body: String },
+    /// We never got an answer from the server.
+    Transport { url: String, error: String },
+    /// Reading or writing the cache failed.
+    Cache { path: PathBuf, error: std::io::Error },
+}
+
+impl Display for ProviderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProviderError::MissingSession => write!(f, "Input isn't cached and {} is not set to fetch it.", SESSION_ENV_VAR),
+            ProviderError::Status { url, status, body } => write!(f, "{} returned {}: {}", url, status, body.trim()),
+            ProviderError::Transport { url, error } => write!(f, "Could not fetch {}: {}", url, error),
+            ProviderError::Cache { path, error } => write!(f, "Could not use the cache at {}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for ProviderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ProviderError::Cache { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ureq::Error> for ProviderError {
+    fn from(error: ureq::Error) -> Self {
+        match error {
+            ureq::Error::Status(status, response) => ProviderError::Status {
+                url: response.get_url().to_string(),
+                status,
+                body: response.into_string().unwrap_or_default(),
+            },
+            ureq::Error::Transport(transport) => ProviderError::Transport {
+                url: transport.url().map(|url| url.to_string()).unwrap_or_default(),
+                error: transport.kind().to_string(),
+            },
+        }
+    }
+}
+
+
+/// Puzzle inputs, kept in an on-disk cache keyed by year and day
+/// and only fetched from `base_url` when they're not in there yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputProvider {
+    pub base_url: String,
+    pub session: Option<String>,
+    pub cache_dir: PathBuf,
+}
+
+impl InputProvider {
+    pub fn new(base_url: &str, session: Option<String>, cache_dir: PathBuf) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            cache_dir,
+        }
+    }
+
+    /// Configured from `AOC_BASE_URL`, `AOC_SESSION_ID` and the default cache directory.
+    pub fn from_env() -> Self {
+        let base_url = std::env::var(BASE_URL_ENV_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        let cache_dir = default_cache_dir().unwrap_or_else(|| std::env::temp_dir().join("aoc"));
+        Self::new(&base_url, std::env::var(SESSION_ENV_VAR).ok(), cache_dir)
+    }
+
+    pub fn url(&self, year: u16, day: u8) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, year, day)
+    }
+
+    pub fn cached(&self, year: u16, day: u8) -> Option<String> {
+        std::fs::read_to_string(cached_input_path(&self.cache_dir, year, day)).ok()
+    }
+
+    /// The input for that day, straight from the cache if we've seen it before.
+    pub fn get(&self, year: u16, day: u8) -> Result<String, ProviderError> {
+        if let Some(input) = self.cached(year, day) {
+            return Ok(input);
+        }
+
+        let input = self.fetch(year, day)?;
+
+        let path = cached_input_path(&self.cache_dir, year, day);
+        path.parent()
+            .map(std::fs::create_dir_all)
+            .transpose()
+            .and_then(|_| std::fs::write(&path, &input))
+            .map_err(|error| ProviderError::Cache { path, error })?;
+
+        Ok(input)
+    }
+
+    /// Always goes to the server, never touches the cache.
+    pub fn fetch(&self, year: u16, day: u8) -> Result<String, ProviderError> {
+        let session = self.session.as_ref().ok_or(ProviderError::MissingSession)?;
+        let url = self.url(year, day);
+
+        let response = ureq::get(&url)
+            .set("User-Agent", USER_AGENT)
+            .set("Cookie", &format!("session={}", session))
+            .call()?;
+
+        response
+            .into_string()
+            .map_err(|error| ProviderError::Transport { url, error: error.to_string() })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stand_in;
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("advent-provider-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn fetches_only_on_cache_miss() {
+        let (base_url, server) = stand_in::serve(vec![(200, "1\n2\n3\n")]);
+        let provider = InputProvider::new(&base_url, Some("cookie".to_string()), cache_dir("miss"));
+
+        assert_eq!(provider.cached(2022, 1), None);
+        assert_eq!(provider.get(2022, 1).unwrap(), "1\n2\n3\n");
+        assert_eq!(provider.get(2022, 1).unwrap(), "1\n2\n3\n");
+        assert_eq!(provider.cached(2022, 1).unwrap(), "1\n2\n3\n");
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2022/day/1/input");
+        assert_eq!(requests[0].header("Cookie"), Some("session=cookie"));
+        assert_eq!(requests[0].header("User-Agent"), Some(USER_AGENT));
+    }
+
+    #[test]
+    fn errors_are_not_cached() {
+        let (base_url, server) = stand_in::serve(vec![(404, "Not found")]);
+        let provider = InputProvider::new(&base_url, Some("cookie".to_string()), cache_dir("error"));
+
+        assert!(matches!(provider.get(2022, 25), Err(ProviderError::Status { status: 404, .. })));
+        assert_eq!(provider.cached(2022, 25), None);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn needs_a_session_to_fetch() {
+        let provider = InputProvider::new("http://127.0.0.1:1", None, cache_dir("session"));
+        assert!(matches!(provider.get(2022, 1), Err(ProviderError::MissingSession)));
+    }
+}
@@ -0,0 +1,54 @@
+//! A seeded random number generator for tests that want plenty of inputs,
+//! and the same ones on every run so failures can be reproduced.
+
+use std::ops::RangeInclusive;
+
+
+/// Small deterministic xorshift. Not much use for anything but tests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng(u64);
+
+impl Rng {
+    /// The seed can't be zero, xorshift would only ever give zero back.
+    pub fn new(seed: u64) -> Self {
+        assert_ne!(seed, 0, "A xorshift seed can't be zero.");
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Somewhere in `range`, bounds included.
+    pub fn range(&mut self, range: RangeInclusive<isize>) -> isize {
+        let span = (range.end() - range.start() + 1) as u64;
+        range.start() + (self.next_u64() % span) as isize
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let (mut a, mut b) = (Rng::new(2022), Rng::new(2022));
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn stays_in_range() {
+        let mut rng = Rng::new(14);
+        let rolls = (0..1000).map(|_| rng.range(-3..=3)).collect::<Vec<_>>();
+        assert!(rolls.iter().all(|roll| (-3..=3).contains(roll)));
+        assert!((-3..=3).all(|side| rolls.contains(&side)));
+        assert_eq!(rng.range(5..=5), 5);
+    }
+}
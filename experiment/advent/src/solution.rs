@@ -0,0 +1,147 @@
+use std::error::Error;
+use std::fmt::Display;
+
+
+/// A single day's puzzle: parse the raw input once, then run either part on it.
+pub trait Solution {
+    type Input;
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    fn parse(&self, input: &str) -> Self::Input;
+    fn part1(&self, input: &Self::Input) -> Self::Answer1;
+    fn part2(&self, input: &Self::Input) -> Self::Answer2;
+}
+
+
+/// What a part hands back: a value to print, or a [`Result`]
+/// whose error gets reported instead of the part panicking.
+pub trait Answer {
+    fn into_answer(self) -> Result<String, Box<dyn Error>>;
+}
+
+macro_rules! impl_answer_for_display {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                fn into_answer(self) -> Result<String, Box<dyn Error>> {
+                    Ok(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_for_display!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, String, Unsolved);
+
+impl<T: Answer, E: Error + 'static> Answer for Result<T, E> {
+    fn into_answer(self) -> Result<String, Box<dyn Error>> {
+        self?.into_answer()
+    }
+}
+
+
+/// Stand-in answer for a part that doesn't have a solution yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsolved;
+
+impl Display for Unsolved {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unsolved")
+    }
+}
+
+
+/// Type-erased view of a [`Solution`] so that tools can keep
+/// every day behind the same `Box<dyn Solver>`.
+pub trait Solver {
+    /// Parse the raw input and run the given part on it.
+    /// Returns `None` if `part` is neither 1 nor 2, and the part's error if it failed.
+    fn solve(&self, part: u8, input: &str) -> Option<Result<String, Box<dyn Error>>>;
+}
+
+impl<S: Solution> Solver for S {
+    fn solve(&self, part: u8, input: &str) -> Option<Result<String, Box<dyn Error>>> {
+        match part {
+            1 => Some(self.part1(&self.parse(input)).into_answer()),
+            2 => Some(self.part2(&self.parse(input)).into_answer()),
+            _ => None
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i64>;
+        type Answer1 = i64;
+        type Answer2 = Unsolved;
+
+        fn parse(&self, input: &str) -> Self::Input {
+            input.lines().map(|line| line.parse().unwrap()).collect()
+        }
+        fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+            input.iter().sum()
+        }
+        fn part2(&self, _input: &Self::Input) -> Self::Answer2 {
+            Unsolved
+        }
+    }
+
+    /// Like [`Sum`], but refuses to add up negative numbers.
+    struct PositiveSum;
+
+    #[derive(Debug)]
+    struct Negative(i64);
+
+    impl Display for Negative {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{} is negative", self.0)
+        }
+    }
+
+    impl Error for Negative {}
+
+    impl Solution for PositiveSum {
+        type Input = Vec<i64>;
+        type Answer1 = Result<i64, Negative>;
+        type Answer2 = Unsolved;
+
+        fn parse(&self, input: &str) -> Self::Input {
+            Sum.parse(input)
+        }
+        fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+            match input.iter().find(|&&n| n < 0) {
+                Some(&n) => Err(Negative(n)),
+                None => Ok(input.iter().sum()),
+            }
+        }
+        fn part2(&self, _input: &Self::Input) -> Self::Answer2 {
+            Unsolved
+        }
+    }
+
+    fn answer(solver: &dyn Solver, part: u8, input: &str) -> Option<Result<String, String>> {
+        solver.solve(part, input).map(|answer| answer.map_err(|err| err.to_string()))
+    }
+
+    #[test]
+    fn solver_dispatches_parts() {
+        let solver: Box<dyn Solver> = Box::new(Sum);
+        assert_eq!(answer(&*solver, 1, "1\n2\n3"), Some(Ok("6".to_string())));
+        assert_eq!(answer(&*solver, 2, "1\n2\n3"), Some(Ok("unsolved".to_string())));
+        assert_eq!(answer(&*solver, 3, "1\n2\n3"), None);
+    }
+
+    #[test]
+    fn solver_passes_errors_on() {
+        let solver: Box<dyn Solver> = Box::new(PositiveSum);
+        assert_eq!(answer(&*solver, 1, "1\n2\n3"), Some(Ok("6".to_string())));
+        assert_eq!(answer(&*solver, 1, "1\n-2\n3"), Some(Err("-2 is negative".to_string())));
+    }
+}
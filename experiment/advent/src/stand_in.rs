@@ -0,0 +1,91 @@
+//! A tiny HTTP server that replays canned responses, so that anything
+//! talking to adventofcode.com can be tested without it.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread::JoinHandle;
+
+
+/// A request as the stand-in saw it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+
+/// Serve each of `responses` (status, body) once, in order, then stop.
+///
+/// Returns the base url to point clients at, and a handle that yields
+/// every request received once all the responses have been served.
+pub fn serve(responses: Vec<(u16, &str)>) -> (String, JoinHandle<Vec<Request>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let responses = responses
+        .into_iter()
+        .map(|(status, body)| (status, body.to_string()))
+        .collect::<Vec<_>>();
+
+    let handle = std::thread::spawn(move || {
+        let mut requests = vec![];
+
+        for (status, body) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut parts = request_line.split_whitespace();
+            let method = parts.next().unwrap_or_default().to_string();
+            let path = parts.next().unwrap_or_default().to_string();
+
+            let mut headers = vec![];
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some((key, value)) = line.split_once(':') {
+                    headers.push((key.trim().to_string(), value.trim().to_string()));
+                }
+            }
+
+            let mut request = Request { method, path, headers, body: String::new() };
+            let length = request
+                .header("Content-Length")
+                .and_then(|length| length.parse::<usize>().ok())
+                .unwrap_or(0);
+            let mut buffer = vec![0; length];
+            reader.read_exact(&mut buffer).unwrap();
+            request.body = String::from_utf8(buffer).unwrap();
+            requests.push(request);
+
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 {} Stand-in\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            ).unwrap();
+            stream.flush().unwrap();
+        }
+
+        requests
+    });
+
+    (base_url, handle)
+}
@@ -0,0 +1,518 @@
+use std::fmt::Display;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{cache_day_dir, default_cache_dir, ProviderError, BASE_URL_ENV_VAR, DEFAULT_BASE_URL, SESSION_ENV_VAR, USER_AGENT};
+
+
+/// How long Advent of Code makes you wait after a wrong answer,
+/// if the response doesn't say.
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60);
+
+
+/// What the server made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint which way.
+    Incorrect,
+    /// Not checked at all, because the last answer was too recent.
+    TooRecent(Duration),
+    /// The part is already solved, or not unlocked yet.
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Read the verdict off the page returned for a submission.
+    pub fn parse(text: &str) -> Result<Self, SubmitError> {
+        if text.contains("That's the right answer") {
+            Ok(Verdict::Correct)
+        } else if text.contains("your answer is too high") {
+            Ok(Verdict::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Ok(Verdict::TooLow)
+        } else if text.contains("That's not the right answer") {
+            Ok(Verdict::Incorrect)
+        } else if text.contains("You gave an answer too recently") {
+            Ok(Verdict::TooRecent(parse_time_left(text)?.unwrap_or(DEFAULT_COOLDOWN)))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Ok(Verdict::WrongLevel)
+        } else {
+            Err(SubmitError::UnexpectedResponse(text.to_string()))
+        }
+    }
+
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect)
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooRecent(_) => "too-recent",
+            Verdict::WrongLevel => "wrong-level",
+        }
+    }
+
+    fn from_str(s: &str, wait: Duration) -> Option<Self> {
+        match s {
+            "correct" => Some(Verdict::Correct),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            "incorrect" => Some(Verdict::Incorrect),
+            "too-recent" => Some(Verdict::TooRecent(wait)),
+            "wrong-level" => Some(Verdict::WrongLevel),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::TooHigh => write!(f, "That's not the right answer; it is too high."),
+            Verdict::TooLow => write!(f, "That's not the right answer; it is too low."),
+            Verdict::Incorrect => write!(f, "That's not the right answer."),
+            Verdict::TooRecent(wait) => write!(f, "Answered too recently; {}s left to wait.", wait.as_secs()),
+            Verdict::WrongLevel => write!(f, "That part is already solved or not unlocked yet."),
+        }
+    }
+}
+
+
+/// "You have 1m 38s left to wait."
+/// `None` if the page doesn't say, and an error if it does but we can't read it.
+fn parse_time_left(text: &str) -> Result<Option<Duration>, SubmitError> {
+    let Some(start) = text.find("You have ").map(|start| start + "You have ".len()) else {
+        return Ok(None);
+    };
+    let Some(end) = text[start..].find(" left to wait").map(|end| start + end) else {
+        return Ok(None);
+    };
+
+    let seconds = |part: &str| {
+        if let Some(n) = part.strip_suffix('m') {
+            n.parse::<u64>().ok().map(|n| n * 60)
+        } else if let Some(n) = part.strip_suffix('s') {
+            n.parse::<u64>().ok()
+        } else {
+            None
+        }
+    };
+
+    text[start..end]
+        .split_whitespace()
+        .map(seconds)
+        .sum::<Option<u64>>()
+        .map(|total| Some(Duration::from_secs(total)))
+        .ok_or_else(|| SubmitError::UnexpectedResponse(text.to_string()))
+}
+
+/// "Please wait one minute before trying again." or "...wait 5 minutes before..."
+fn parse_cooldown(text: &str) -> Option<Duration> {
+    let lower = text.to_lowercase();
+    let start = lower.find("please wait ")? + "please wait ".len();
+    let mut words = lower[start..].split_whitespace();
+
+    let count = match words.next()? {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "ten" => 10,
+        n => n.parse().ok()?,
+    };
+    let seconds = match words.next()? {
+        unit if unit.starts_with("minute") => 60,
+        unit if unit.starts_with("second") => 1,
+        _ => return None,
+    };
+    Some(Duration::from_secs(count * seconds))
+}
+
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+
+/// A single answer we sent in, and what came back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the epoch.
+    pub submitted_at: u64,
+    /// No submission for the day should go out before this.
+    pub retry_at: u64,
+}
+
+impl Attempt {
+    fn to_line(&self) -> String {
+        let answer = escape(&self.answer);
+        format!("{}\t{}\t{}\t{}\t{}", self.part, self.submitted_at, self.retry_at, self.verdict.as_str(), answer)
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(5, '\t');
+        let part = fields.next()?.parse().ok()?;
+        let submitted_at: u64 = fields.next()?.parse().ok()?;
+        let retry_at: u64 = fields.next()?.parse().ok()?;
+        let wait = Duration::from_secs(retry_at.saturating_sub(submitted_at));
+        let verdict = Verdict::from_str(fields.next()?, wait)?;
+        let answer = unescape(fields.next()?)?;
+        Some(Self { part, answer, verdict, submitted_at, retry_at })
+    }
+}
+
+/// Answers like a rendered CRT screen span several lines, so tabs, newlines
+/// and backslashes get escaped to keep every attempt on one line of the ledger.
+fn escape(answer: &str) -> String {
+    let mut escaped = String::with_capacity(answer.len());
+    for character in answer.chars() {
+        match character {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+fn unescape(escaped: &str) -> Option<String> {
+    let mut answer = String::with_capacity(escaped.len());
+    let mut characters = escaped.chars();
+    while let Some(character) = characters.next() {
+        answer.push(match character {
+            '\\' => match characters.next()? {
+                '\\' => '\\',
+                't' => '\t',
+                'n' => '\n',
+                'r' => '\r',
+                _ => return None,
+            },
+            character => character,
+        });
+    }
+    Some(answer)
+}
+
+
+/// Every answer attempted for a day, kept next to its cached input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ledger {
+    pub path: PathBuf,
+    pub attempts: Vec<Attempt>,
+}
+
+impl Ledger {
+    pub fn open(cache_dir: &Path, year: u16, day: u8) -> Result<Self, SubmitError> {
+        let path = cache_day_dir(cache_dir, year, day).join("answers.tsv");
+
+        let attempts = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents.lines().filter_map(Attempt::from_line).collect(),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(error) => return Err(SubmitError::Ledger { path, error }),
+        };
+
+        Ok(Self { path, attempts })
+    }
+
+    /// Whether `answer` may be sent in for `part` at time `now`.
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<(), SubmitError> {
+        let attempts = self.attempts.iter().filter(|attempt| attempt.part == part);
+
+        if let Some(solved) = attempts.clone().find(|attempt| attempt.verdict == Verdict::Correct) {
+            return Err(SubmitError::AlreadySolved { answer: solved.answer.clone() });
+        }
+
+        for attempt in attempts.clone() {
+            if attempt.verdict.is_wrong() && attempt.answer == answer {
+                return Err(SubmitError::KnownWrong { answer: attempt.answer.clone(), verdict: attempt.verdict });
+            }
+        }
+
+        // Anything past a bound we've already been told about is wrong too.
+        if let Ok(value) = answer.parse::<i128>() {
+            for attempt in attempts {
+                let ruled_out = match (attempt.verdict, attempt.answer.parse::<i128>()) {
+                    (Verdict::TooHigh, Ok(bound)) => value >= bound,
+                    (Verdict::TooLow, Ok(bound)) => value <= bound,
+                    _ => false,
+                };
+                if ruled_out {
+                    return Err(SubmitError::KnownWrong { answer: attempt.answer.clone(), verdict: attempt.verdict });
+                }
+            }
+        }
+
+        let retry_at = self.attempts.iter().map(|attempt| attempt.retry_at).max().unwrap_or(0);
+        if now < retry_at {
+            return Err(SubmitError::Cooldown { remaining: Duration::from_secs(retry_at - now) });
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), SubmitError> {
+        let to_ledger_error = |error| SubmitError::Ledger { path: self.path.clone(), error };
+
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).map_err(to_ledger_error)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(to_ledger_error)?;
+        writeln!(file, "{}", attempt.to_line()).map_err(to_ledger_error)?;
+
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+
+#[derive(Debug)]
+pub enum SubmitError {
+    MissingSession,
+    /// That part already has an accepted answer.
+    AlreadySolved { answer: String },
+    /// `answer` was already rejected with `verdict`, and that rules this one out.
+    KnownWrong { answer: String, verdict: Verdict },
+    /// Submitting now would only get us told off.
+    Cooldown { remaining: Duration },
+    /// The page didn't look like any response we know of.
+    UnexpectedResponse(String),
+    Request(ProviderError),
+    Ledger { path: PathBuf, error: std::io::Error },
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitError::MissingSession => write!(f, "{} must be set to submit answers.", SESSION_ENV_VAR),
+            SubmitError::AlreadySolved { answer } => write!(f, "Already solved with {}.", answer),
+            SubmitError::KnownWrong { answer, verdict } => write!(f, "Not submitting: {} was already rejected ({})", answer, verdict),
+            SubmitError::Cooldown { remaining } => write!(f, "Not submitting: {}s left to wait.", remaining.as_secs()),
+            SubmitError::UnexpectedResponse(text) => write!(f, "Could not make sense of the response: {}", text.trim()),
+            SubmitError::Request(error) => write!(f, "{}", error),
+            SubmitError::Ledger { path, error } => write!(f, "Could not use the answer ledger at {}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SubmitError::Request(error) => Some(error),
+            SubmitError::Ledger { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ureq::Error> for SubmitError {
+    fn from(error: ureq::Error) -> Self {
+        SubmitError::Request(error.into())
+    }
+}
+
+
+/// Posts answers to `base_url`, but only the ones the day's [`Ledger`] lets through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submitter {
+    pub base_url: String,
+    pub session: Option<String>,
+    pub cache_dir: PathBuf,
+}
+
+impl Submitter {
+    pub fn new(base_url: &str, session: Option<String>, cache_dir: PathBuf) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            cache_dir,
+        }
+    }
+
+    /// Configured from `AOC_BASE_URL`, `AOC_SESSION_ID` and the default cache directory.
+    pub fn from_env() -> Self {
+        let base_url = std::env::var(BASE_URL_ENV_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        let cache_dir = default_cache_dir().unwrap_or_else(|| std::env::temp_dir().join("aoc"));
+        Self::new(&base_url, std::env::var(SESSION_ENV_VAR).ok(), cache_dir)
+    }
+
+    pub fn url(&self, year: u16, day: u8) -> String {
+        format!("{}/{}/day/{}/answer", self.base_url, year, day)
+    }
+
+    pub fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<Verdict, SubmitError> {
+        let answer = answer.trim();
+        let mut ledger = Ledger::open(&self.cache_dir, year, day)?;
+        let submitted_at = now();
+        ledger.check(part, answer, submitted_at)?;
+
+        let session = self.session.as_ref().ok_or(SubmitError::MissingSession)?;
+        let url = self.url(year, day);
+        let text = ureq::post(&url)
+            .set("User-Agent", USER_AGENT)
+            .set("Cookie", &format!("session={}", session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()
+            .map_err(|error| SubmitError::Request(ProviderError::Transport { url, error: error.to_string() }))?;
+
+        let verdict = Verdict::parse(&text)?;
+        let wait = match verdict {
+            Verdict::TooRecent(wait) => wait,
+            verdict if verdict.is_wrong() => parse_cooldown(&text).unwrap_or(DEFAULT_COOLDOWN),
+            _ => Duration::ZERO,
+        };
+
+        ledger.record(Attempt {
+            part,
+            answer: answer.to_string(),
+            verdict,
+            submitted_at,
+            retry_at: submitted_at + wait.as_secs(),
+        })?;
+
+        Ok(verdict)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stand_in;
+    use test_case::test_case;
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("advent-submit-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn attempt(part: u8, answer: &str, verdict: Verdict, submitted_at: u64, retry_at: u64) -> Attempt {
+        Attempt { part, answer: answer.to_string(), verdict, submitted_at, retry_at }
+    }
+
+    #[test_case("<article><p>That's the right answer! You are one gold star closer.</p></article>", Verdict::Correct; "correct")]
+    #[test_case("<article><p>That's not the right answer; your answer is too high.  If you're stuck, ... Please wait one minute before trying again.</p></article>", Verdict::TooHigh; "too high")]
+    #[test_case("<article><p>That's not the right answer; your answer is too low.</p></article>", Verdict::TooLow; "too low")]
+    #[test_case("<article><p>That's not the right answer.  If you're stuck, ...</p></article>", Verdict::Incorrect; "incorrect")]
+    #[test_case("<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 38s left to wait.</p></article>", Verdict::TooRecent(Duration::from_secs(38)); "too recent seconds")]
+    #[test_case("<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 2s left to wait.</p></article>", Verdict::TooRecent(Duration::from_secs(242)); "too recent minutes")]
+    #[test_case("<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>", Verdict::WrongLevel; "wrong level")]
+    fn parse_verdict(text: &str, expected: Verdict) {
+        assert_eq!(Verdict::parse(text).unwrap(), expected);
+    }
+
+    #[test_case("You have 4é left to wait."; "multibyte unit")]
+    #[test_case("You have 4 left to wait."; "no unit")]
+    #[test_case("You have m left to wait."; "no number")]
+    fn unreadable_time_left(text: &str) {
+        assert!(matches!(parse_time_left(text), Err(SubmitError::UnexpectedResponse(page)) if page == text));
+    }
+
+    #[test]
+    fn too_recent_without_a_time_waits_the_default() {
+        let text = "You gave an answer too recently; you have to wait after submitting an answer before trying again.";
+        assert_eq!(Verdict::parse(text).unwrap(), Verdict::TooRecent(DEFAULT_COOLDOWN));
+    }
+
+    #[test_case("Please wait one minute before trying again.", Some(60))]
+    #[test_case("please wait 5 minutes before trying again.", Some(300))]
+    #[test_case("That's not the right answer.", None)]
+    fn parse_wrong_answer_cooldown(text: &str, expected: Option<u64>) {
+        assert_eq!(parse_cooldown(text), expected.map(Duration::from_secs));
+    }
+
+    #[test]
+    fn ledger_refuses_known_wrong_answers() {
+        let dir = cache_dir("ledger");
+        let mut ledger = Ledger::open(&dir, 2022, 1).unwrap();
+        ledger.record(attempt(1, "100", Verdict::TooHigh, 0, 60)).unwrap();
+        ledger.record(attempt(1, "10", Verdict::TooLow, 60, 120)).unwrap();
+        ledger.record(attempt(1, "abc", Verdict::Incorrect, 120, 180)).unwrap();
+
+        assert!(matches!(ledger.check(1, "100", 1000), Err(SubmitError::KnownWrong { verdict: Verdict::TooHigh, .. })));
+        assert!(matches!(ledger.check(1, "150", 1000), Err(SubmitError::KnownWrong { verdict: Verdict::TooHigh, .. })));
+        assert!(matches!(ledger.check(1, "3", 1000), Err(SubmitError::KnownWrong { verdict: Verdict::TooLow, .. })));
+        assert!(matches!(ledger.check(1, "abc", 1000), Err(SubmitError::KnownWrong { verdict: Verdict::Incorrect, .. })));
+        assert!(ledger.check(1, "50", 1000).is_ok());
+        assert!(ledger.check(2, "100", 1000).is_ok());
+
+        assert!(matches!(ledger.check(1, "50", 150), Err(SubmitError::Cooldown { remaining }) if remaining.as_secs() == 30));
+
+        // And it all survives a round trip through the file.
+        assert_eq!(Ledger::open(&dir, 2022, 1).unwrap(), ledger);
+    }
+
+    #[test]
+    fn multi_line_answers_stay_on_one_line() {
+        let dir = cache_dir("multi-line");
+        let mut ledger = Ledger::open(&dir, 2022, 10).unwrap();
+        let screen = "##..##\n#\t.\\.#\r\n";
+        ledger.record(attempt(2, screen, Verdict::Incorrect, 0, 60)).unwrap();
+        ledger.record(attempt(1, "13140", Verdict::Correct, 60, 60)).unwrap();
+
+        let contents = std::fs::read_to_string(&ledger.path).unwrap();
+        assert_eq!(contents.lines().count(), 2);
+        assert_eq!(Ledger::open(&dir, 2022, 10).unwrap(), ledger);
+        assert!(matches!(ledger.check(2, screen, 1000), Err(SubmitError::KnownWrong { .. })));
+    }
+
+    #[test]
+    fn submits_and_records_verdicts() {
+        let (base_url, server) = stand_in::serve(vec![
+            (200, "<article><p>That's not the right answer; your answer is too high.  Please wait one minute before trying again.</p></article>"),
+        ]);
+        let submitter = Submitter::new(&base_url, Some("cookie".to_string()), cache_dir("submit"));
+
+        assert_eq!(submitter.submit(2022, 6, 1, "1234\n").unwrap(), Verdict::TooHigh);
+        // Neither of these reach the server.
+        assert!(matches!(submitter.submit(2022, 6, 1, "1234"), Err(SubmitError::KnownWrong { .. })));
+        assert!(matches!(submitter.submit(2022, 6, 1, "1000"), Err(SubmitError::Cooldown { .. })));
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2022/day/6/answer");
+        assert_eq!(requests[0].body, "level=1&answer=1234");
+        assert_eq!(requests[0].header("Cookie"), Some("session=cookie"));
+
+        let ledger = Ledger::open(&submitter.cache_dir, 2022, 6).unwrap();
+        assert_eq!(ledger.attempts.len(), 1);
+        assert_eq!(ledger.attempts[0].retry_at - ledger.attempts[0].submitted_at, 60);
+    }
+
+    #[test]
+    fn solved_parts_are_not_resubmitted() {
+        let (base_url, server) = stand_in::serve(vec![
+            (200, "<article><p>That's the right answer! You are one gold star closer.</p></article>"),
+        ]);
+        let submitter = Submitter::new(&base_url, Some("cookie".to_string()), cache_dir("solved"));
+        let mut ledger = Ledger::open(&submitter.cache_dir, 2022, 6).unwrap();
+        ledger.record(attempt(2, "1", Verdict::TooLow, 0, 60)).unwrap();
+
+        assert_eq!(submitter.submit(2022, 6, 2, "42").unwrap(), Verdict::Correct);
+        assert!(matches!(submitter.submit(2022, 6, 2, "43"), Err(SubmitError::AlreadySolved { answer }) if answer == "42"));
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use advent::Solver;
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    UnknownDay(u8),
+    UnknownPart(u8),
+    /// The solution ran, but couldn't come up with an answer.
+    Unsolvable { day: u8, part: u8, reason: String },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::UnknownDay(day) => write!(f, "No solutions exist for day {}.", day),
+            Error::UnknownPart(part) => write!(f, "Part must be one of 1 or 2 (got {}).", part),
+            Error::Unsolvable { day, part, reason } => write!(f, "Day {} part {} has no answer: {}", day, part, reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+
+/// Every day that has a Rust solution, keyed by day.
+pub fn solutions() -> HashMap<u8, Box<dyn Solver>> {
+    let mut solutions: HashMap<u8, Box<dyn Solver>> = HashMap::new();
+
+    solutions.insert(1, Box::new(day_01::Day01));
+    solutions.insert(2, Box::new(day_02::Day02));
+    solutions.insert(3, Box::new(day_03::Day03));
+    solutions.insert(4, Box::new(day_04::Day04));
+    solutions.insert(5, Box::new(day_05::Day05));
+    solutions.insert(6, Box::new(day_06::Day06));
+    solutions.insert(7, Box::new(day_07::Day07));
+    solutions.insert(8, Box::new(day_08::Day08));
+    solutions.insert(9, Box::new(day_09::Day09));
+    solutions.insert(10, Box::new(day_10::Day10));
+    solutions.insert(11, Box::new(day_11::Day11));
+    solutions.insert(12, Box::new(day_12::Day12));
+    solutions.insert(13, Box::new(day_13::Day13));
+    solutions.insert(14, Box::new(day_14::Day14));
+    solutions.insert(15, Box::new(day_15::Day15));
+    solutions.insert(16, Box::new(day_16::Day16));
+    solutions.insert(17, Box::new(day_17::Day17));
+    solutions.insert(18, Box::new(day_18::Day18));
+    solutions.insert(19, Box::new(day_19::Day19));
+    solutions.insert(20, Box::new(day_20::Day20));
+    solutions.insert(21, Box::new(day_21::Day21));
+    solutions.insert(22, Box::new(day_22::Day22));
+
+    solutions
+}
+
+
+/// Run the solution for the given day and part against the raw puzzle input.
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, Error> {
+    let solutions = solutions();
+    let solution = solutions.get(&day).ok_or(Error::UnknownDay(day))?;
+    solution
+        .solve(part, input)
+        .ok_or(Error::UnknownPart(part))?
+        .map_err(|err| Error::Unsolvable { day, part, reason: err.to_string() })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dispatches_to_day() {
+        assert_eq!(solve(6, 1, "mjqjpqmgbljsphdztnvjfqwrcgsmlb"), Ok("7".to_string()));
+        assert_eq!(solve(6, 2, "mjqjpqmgbljsphdztnvjfqwrcgsmlb"), Ok("19".to_string()));
+        assert_eq!(solve(8, 1, "30373\n25512\n65332\n33549\n35390\n"), Ok("21".to_string()));
+        assert_eq!(solve(4, 2, "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n"), Ok("4".to_string()));
+    }
+
+    #[test]
+    fn rejects_unknown_days_and_parts() {
+        assert_eq!(solve(6, 3, ""), Err(Error::UnknownPart(3)));
+        assert_eq!(solve(26, 1, ""), Err(Error::UnknownDay(26)));
+        assert_eq!(solve(23, 1, ""), Err(Error::UnknownDay(23)));
+    }
+
+    #[test]
+    fn reports_parts_without_an_answer() {
+        assert_eq!(
+            solve(21, 2, "root: double + seven\ndouble: two * humn\nhumn: 0\ntwo: 2\nseven: 7"),
+            Err(Error::Unsolvable { day: 21, part: 2, reason: "humn is 7/2, not a whole number".to_string() })
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use advent::{InputError, InputLoader, InputProvider, Submitter, SESSION_ENV_VAR};
+use clap::Parser;
+
+
+/// Run Advent Of Code 2022 solutions against any puzzle input.
+#[derive(Parser, Debug)]
+#[command(name = "aoc")]
+struct Args {
+    /// The day to run solutions for.
+    #[arg(short, long)]
+    day: u8,
+
+    /// The part to run solution for.
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// Path to the puzzle input, or `-` to read it from stdin.
+    /// Falls back to `$AOC_INPUT`, then to the input cache,
+    /// which gets filled from adventofcode.com if `$AOC_SESSION_ID` is set.
+    #[arg(short, long)]
+    input: Option<String>,
+
+    /// Submit the answer to adventofcode.com (needs `$AOC_SESSION_ID`).
+    /// Answers that are known to be wrong are never sent twice.
+    #[arg(short, long)]
+    submit: bool,
+}
+
+
+fn run(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let mut loader = InputLoader::new(args.day);
+    loader.path = args.input.clone();
+    let input = match loader.load() {
+        Err(InputError::Missing { year, day }) if std::env::var_os(SESSION_ENV_VAR).is_some() => {
+            InputProvider::from_env().get(year, day)?
+        },
+        input => input?,
+    };
+
+    let answer = aoc::solve(args.day, args.part, &input)?;
+    println!("(Day: {}, Part: {}) Answer: {}", args.day, args.part, answer);
+
+    if args.submit {
+        let verdict = Submitter::from_env().submit(loader.year, args.day, args.part, &answer)?;
+        println!("{}", verdict);
+    }
+    Ok(())
+}
+
+
+fn main() {
+    let args = Args::parse();
+
+    if let Err(err) = run(&args) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
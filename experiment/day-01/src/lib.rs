@@ -0,0 +1,82 @@
+mod parser;
+pub use parser::*;
+
+use advent::Solution;
+
+
+pub fn get_elves(s: &str) -> Vec<Vec<Calories>> {
+    parse_elves(s.trim()).unwrap().1
+}
+
+/// Total calories per elf, most first.
+pub fn totals(elves: &[Vec<Calories>]) -> Vec<Calories> {
+    let mut totals = elves
+        .iter()
+        .map(|snacks| snacks.iter().sum())
+        .collect::<Vec<Calories>>();
+    totals.sort_unstable_by(|a, b| b.cmp(a));
+    totals
+}
+
+pub fn solve_part1(s: &str) -> Calories {
+    totals(&get_elves(s)).into_iter().take(1).sum()
+}
+
+pub fn solve_part2(s: &str) -> Calories {
+    totals(&get_elves(s)).into_iter().take(3).sum()
+}
+
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<Vec<Calories>>;
+    type Answer1 = Calories;
+    type Answer2 = Calories;
+
+    fn parse(&self, input: &str) -> Self::Input {
+        get_elves(input)
+    }
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        totals(input).into_iter().take(1).sum()
+    }
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        totals(input).into_iter().take(3).sum()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../../../aoc/pkg/year_2022/testdata/day-01/input.txt");
+    const ANSWERS: &str = include_str!("../../../aoc/pkg/year_2022/testdata/day-01/answers.txt");
+
+    #[test]
+    fn smol() {
+        let s = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+        assert_eq!(solve_part1(s), 24000);
+        assert_eq!(solve_part2(s), 45000);
+    }
+
+    #[test]
+    fn matches_go_fixture() {
+        let answers = ANSWERS.lines().collect::<Vec<_>>();
+        assert_eq!(solve_part1(FIXTURE).to_string(), answers[0]);
+        assert_eq!(solve_part2(FIXTURE).to_string(), answers[1]);
+    }
+}
@@ -0,0 +1,41 @@
+use nom::{
+    character::complete::*,
+    combinator::map_res,
+    multi::separated_list1,
+    sequence::pair,
+    IResult,
+};
+
+pub type Calories = u64;
+
+fn parse_calories(s: &str) -> IResult<&str, Calories> {
+    map_res(digit1, str::parse::<Calories>)(s)
+}
+
+/// The snacks carried by a single elf, one per line.
+pub fn parse_elf(s: &str) -> IResult<&str, Vec<Calories>> {
+    separated_list1(line_ending, parse_calories)(s)
+}
+
+/// Elves are separated by a blank line.
+pub fn parse_elves(s: &str) -> IResult<&str, Vec<Vec<Calories>>> {
+    separated_list1(pair(line_ending, line_ending), parse_elf)(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_elf() {
+        let (rem, elf) = parse_elf("1000\n2000\n3000\n\n4000").unwrap();
+        assert_eq!(elf, vec![1000, 2000, 3000]);
+        assert_eq!(rem, "\n\n4000");
+    }
+
+    #[test]
+    fn test_parse_elves() {
+        let (_, elves) = parse_elves("1000\n2000\n\n4000\n\n5000\n6000\n").unwrap();
+        assert_eq!(elves, vec![vec![1000, 2000], vec![4000], vec![5000, 6000]]);
+    }
+}
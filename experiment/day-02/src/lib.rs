@@ -0,0 +1,161 @@
+mod parser;
+pub use parser::*;
+
+use advent::Solution;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+/// The second column of the strategy guide.
+/// Part 1 reads it as the shape to play, part 2 as how the round should end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Column {
+    X,
+    Y,
+    Z,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Round {
+    pub opponent: Shape,
+    pub column: Column,
+}
+
+
+impl Shape {
+    const ALL: [Shape; 3] = [Shape::Rock, Shape::Paper, Shape::Scissors];
+
+    pub fn score(&self) -> usize {
+        match self {
+            Shape::Rock => 1,
+            Shape::Paper => 2,
+            Shape::Scissors => 3,
+        }
+    }
+
+    /// How the round ends for us if we play `self` against `opponent`.
+    pub fn against(&self, opponent: Shape) -> Outcome {
+        match (*self as usize + 3 - opponent as usize) % 3 {
+            0 => Outcome::Draw,
+            1 => Outcome::Win,
+            _ => Outcome::Lose,
+        }
+    }
+}
+
+impl Outcome {
+    pub fn score(&self) -> usize {
+        match self {
+            Outcome::Lose => 0,
+            Outcome::Draw => 3,
+            Outcome::Win => 6,
+        }
+    }
+}
+
+impl Column {
+    pub fn as_shape(&self) -> Shape {
+        match self {
+            Column::X => Shape::Rock,
+            Column::Y => Shape::Paper,
+            Column::Z => Shape::Scissors,
+        }
+    }
+
+    pub fn as_outcome(&self) -> Outcome {
+        match self {
+            Column::X => Outcome::Lose,
+            Column::Y => Outcome::Draw,
+            Column::Z => Outcome::Win,
+        }
+    }
+}
+
+impl Round {
+    pub fn score(&self, ours: Shape) -> usize {
+        ours.score() + ours.against(self.opponent).score()
+    }
+
+    pub fn score_as_shape(&self) -> usize {
+        self.score(self.column.as_shape())
+    }
+
+    pub fn score_as_outcome(&self) -> usize {
+        let outcome = self.column.as_outcome();
+        let ours = Shape::ALL
+            .into_iter()
+            .find(|shape| shape.against(self.opponent) == outcome)
+            .unwrap();
+        self.score(ours)
+    }
+}
+
+
+pub fn get_rounds(s: &str) -> Vec<Round> {
+    parse_strategy_guide(s.trim()).unwrap().1
+}
+
+pub fn solve_part1(s: &str) -> usize {
+    get_rounds(s).iter().map(Round::score_as_shape).sum()
+}
+
+pub fn solve_part2(s: &str) -> usize {
+    get_rounds(s).iter().map(Round::score_as_outcome).sum()
+}
+
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Round>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Self::Input {
+        get_rounds(input)
+    }
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        input.iter().map(Round::score_as_shape).sum()
+    }
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        input.iter().map(Round::score_as_outcome).sum()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const FIXTURE: &str = include_str!("../../../aoc/pkg/year_2022/testdata/day-02/input.txt");
+    const ANSWERS: &str = include_str!("../../../aoc/pkg/year_2022/testdata/day-02/answers.txt");
+
+    #[test_case("A Y", 8, 4)]
+    #[test_case("B X", 1, 1)]
+    #[test_case("C Z", 6, 7)]
+    fn score_round(s: &str, as_shape: usize, as_outcome: usize) {
+        let (_, round) = parse_round(s).unwrap();
+        assert_eq!(round.score_as_shape(), as_shape);
+        assert_eq!(round.score_as_outcome(), as_outcome);
+    }
+
+    #[test]
+    fn matches_go_fixture() {
+        let answers = ANSWERS.lines().collect::<Vec<_>>();
+        assert_eq!(solve_part1(FIXTURE).to_string(), answers[0]);
+        assert_eq!(solve_part2(FIXTURE).to_string(), answers[1]);
+    }
+}
@@ -0,0 +1,10 @@
+use advent::{InputError, InputLoader};
+use day_02::*;
+
+fn main() -> Result<(), InputError> {
+    let input = InputLoader::from_args(2).load()?;
+    println!("Part 1: {}", solve_part1(&input));
+    println!("Part 2: {}", solve_part2(&input));
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use nom::{
+    character::complete::*,
+    combinator::map,
+    multi::separated_list1,
+    sequence::separated_pair,
+    IResult,
+};
+
+use crate::{Column, Round, Shape};
+
+fn parse_shape(s: &str) -> IResult<&str, Shape> {
+    map(one_of("ABC"), |c| match c {
+        'A' => Shape::Rock,
+        'B' => Shape::Paper,
+        _ => Shape::Scissors,
+    })(s)
+}
+
+fn parse_column(s: &str) -> IResult<&str, Column> {
+    map(one_of("XYZ"), |c| match c {
+        'X' => Column::X,
+        'Y' => Column::Y,
+        _ => Column::Z,
+    })(s)
+}
+
+pub fn parse_round(s: &str) -> IResult<&str, Round> {
+    map(
+        separated_pair(parse_shape, space1, parse_column),
+        |(opponent, column)| Round { opponent, column },
+    )(s)
+}
+
+pub fn parse_strategy_guide(s: &str) -> IResult<&str, Vec<Round>> {
+    separated_list1(line_ending, parse_round)(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_round() {
+        let (_, round) = parse_round("A Y").unwrap();
+        assert_eq!(round, Round { opponent: Shape::Rock, column: Column::Y });
+    }
+
+    #[test]
+    fn test_parse_strategy_guide() {
+        let (_, rounds) = parse_strategy_guide("A Y\nB X\nC Z\n").unwrap();
+        assert_eq!(rounds.len(), 3);
+        assert_eq!(rounds[2], Round { opponent: Shape::Scissors, column: Column::Z });
+    }
+}
@@ -0,0 +1,129 @@
+mod parser;
+pub use parser::*;
+
+use advent::Solution;
+
+
+/// A set of item types, one bit per priority (1..=52).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Items(u64);
+
+pub fn priority(item: char) -> u32 {
+    match item {
+        'a'..='z' => item as u32 - 'a' as u32 + 1,
+        'A'..='Z' => item as u32 - 'A' as u32 + 27,
+        _ => unreachable!("The parser only lets ascii letters through."),
+    }
+}
+
+impl From<&str> for Items {
+    fn from(s: &str) -> Self {
+        Self(s.chars().fold(0, |set, item| set | 1 << priority(item)))
+    }
+}
+
+impl Items {
+    pub fn intersection(&self, other: &Items) -> Items {
+        Items(self.0 & other.0)
+    }
+
+    pub fn union(&self, other: &Items) -> Items {
+        Items(self.0 | other.0)
+    }
+
+    /// Sum of priorities of every item type in the set.
+    pub fn priority(&self) -> u32 {
+        (1..=52).filter(|p| self.0 & (1 << p) != 0).sum()
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rucksack {
+    pub compartments: [Items; 2],
+}
+
+impl Rucksack {
+    pub fn items(&self) -> Items {
+        self.compartments[0].union(&self.compartments[1])
+    }
+
+    /// The item type that ended up in both compartments.
+    pub fn misplaced(&self) -> Items {
+        self.compartments[0].intersection(&self.compartments[1])
+    }
+}
+
+/// The badge is the only item type carried by all three elves of a group.
+pub fn badge(group: &[Rucksack]) -> Items {
+    group
+        .iter()
+        .map(Rucksack::items)
+        .reduce(|common, items| common.intersection(&items))
+        .unwrap_or_default()
+}
+
+
+pub fn get_rucksacks(s: &str) -> Vec<Rucksack> {
+    parse_rucksacks(s.trim()).unwrap().1
+}
+
+pub fn solve_part1(s: &str) -> u32 {
+    get_rucksacks(s).iter().map(|rucksack| rucksack.misplaced().priority()).sum()
+}
+
+pub fn solve_part2(s: &str) -> u32 {
+    get_rucksacks(s).chunks(3).map(|group| badge(group).priority()).sum()
+}
+
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Rucksack>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Self::Input {
+        get_rucksacks(input)
+    }
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        input.iter().map(|rucksack| rucksack.misplaced().priority()).sum()
+    }
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        input.chunks(3).map(|group| badge(group).priority()).sum()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const FIXTURE: &str = include_str!("../../../aoc/pkg/year_2022/testdata/day-03/input.txt");
+    const ANSWERS: &str = include_str!("../../../aoc/pkg/year_2022/testdata/day-03/answers.txt");
+
+    #[test_case('a', 1)]
+    #[test_case('z', 26)]
+    #[test_case('A', 27)]
+    #[test_case('Z', 52)]
+    fn priorities(item: char, expected: u32) {
+        assert_eq!(priority(item), expected);
+    }
+
+    #[test_case("vJrwpWtwJgWrhcsFMMfFFhFp", 16)]
+    #[test_case("jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL", 38)]
+    #[test_case("PmmdzqPrVvPwwTWBwg", 42)]
+    fn misplaced_item(s: &str, expected: u32) {
+        let (_, rucksack) = parse_rucksack(s).unwrap();
+        assert_eq!(rucksack.misplaced().priority(), expected);
+    }
+
+    #[test]
+    fn matches_go_fixture() {
+        let answers = ANSWERS.lines().collect::<Vec<_>>();
+        assert_eq!(solve_part1(FIXTURE).to_string(), answers[0]);
+        assert_eq!(solve_part2(FIXTURE).to_string(), answers[1]);
+    }
+}
@@ -0,0 +1,43 @@
+use nom::{
+    character::complete::*,
+    combinator::{map, verify},
+    multi::separated_list1,
+    IResult,
+};
+
+use crate::Rucksack;
+
+/// A rucksack's items, split evenly into its two compartments.
+pub fn parse_rucksack(s: &str) -> IResult<&str, Rucksack> {
+    map(
+        verify(alpha1, |items: &str| items.len().is_multiple_of(2)),
+        |items: &str| {
+            let (first, second) = items.split_at(items.len() / 2);
+            Rucksack {
+                compartments: [first.into(), second.into()],
+            }
+        },
+    )(s)
+}
+
+pub fn parse_rucksacks(s: &str) -> IResult<&str, Vec<Rucksack>> {
+    separated_list1(line_ending, parse_rucksack)(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Items;
+
+    #[test]
+    fn test_parse_rucksack() {
+        let (_, rucksack) = parse_rucksack("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap();
+        assert_eq!(rucksack.compartments[0], Items::from("vJrwpWtwJgWr"));
+        assert_eq!(rucksack.compartments[1], Items::from("hcsFMMfFFhFp"));
+    }
+
+    #[test]
+    fn test_parse_rucksack_odd_length() {
+        assert!(parse_rucksack("abc").is_err());
+    }
+}
@@ -0,0 +1,98 @@
+mod parser;
+pub use parser::*;
+
+use advent::Solution;
+
+
+/// An inclusive range of section ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Assignment {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl Assignment {
+    pub fn contains(&self, other: &Assignment) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Assignment) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pair {
+    pub first: Assignment,
+    pub second: Assignment,
+}
+
+impl Pair {
+    pub fn overlaps_completely(&self) -> bool {
+        self.first.contains(&self.second) || self.second.contains(&self.first)
+    }
+
+    pub fn overlaps(&self) -> bool {
+        self.first.overlaps(&self.second)
+    }
+}
+
+
+pub fn get_pairs(s: &str) -> Vec<Pair> {
+    parse_pairs(s.trim()).unwrap().1
+}
+
+pub fn solve_part1(s: &str) -> usize {
+    get_pairs(s).iter().filter(|pair| pair.overlaps_completely()).count()
+}
+
+pub fn solve_part2(s: &str) -> usize {
+    get_pairs(s).iter().filter(|pair| pair.overlaps()).count()
+}
+
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Pair>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Self::Input {
+        get_pairs(input)
+    }
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        input.iter().filter(|pair| pair.overlaps_completely()).count()
+    }
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        input.iter().filter(|pair| pair.overlaps()).count()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const FIXTURE: &str = include_str!("../../../aoc/pkg/year_2022/testdata/day-04/input.txt");
+    const ANSWERS: &str = include_str!("../../../aoc/pkg/year_2022/testdata/day-04/answers.txt");
+
+    #[test_case("2-4,6-8", false, false)]
+    #[test_case("5-7,7-9", false, true)]
+    #[test_case("2-8,3-7", true, true)]
+    #[test_case("6-6,4-6", true, true)]
+    fn overlapping(s: &str, completely: bool, at_all: bool) {
+        let (_, pair) = parse_pair(s).unwrap();
+        assert_eq!(pair.overlaps_completely(), completely);
+        assert_eq!(pair.overlaps(), at_all);
+    }
+
+    #[test]
+    fn matches_go_fixture() {
+        let answers = ANSWERS.lines().collect::<Vec<_>>();
+        assert_eq!(solve_part1(FIXTURE).to_string(), answers[0]);
+        assert_eq!(solve_part2(FIXTURE).to_string(), answers[1]);
+    }
+}
@@ -0,0 +1,48 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::*,
+    combinator::map,
+    multi::separated_list1,
+    sequence::separated_pair,
+    IResult,
+};
+
+use crate::{Assignment, Pair};
+
+/// "2-4"
+pub fn parse_assignment(s: &str) -> IResult<&str, Assignment> {
+    map(
+        separated_pair(u64, tag("-"), u64),
+        |(start, end)| Assignment { start, end },
+    )(s)
+}
+
+/// "2-4,6-8"
+pub fn parse_pair(s: &str) -> IResult<&str, Pair> {
+    map(
+        separated_pair(parse_assignment, tag(","), parse_assignment),
+        |(first, second)| Pair { first, second },
+    )(s)
+}
+
+pub fn parse_pairs(s: &str) -> IResult<&str, Vec<Pair>> {
+    separated_list1(line_ending, parse_pair)(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_assignment() {
+        let (_, assignment) = parse_assignment("12-345").unwrap();
+        assert_eq!(assignment, Assignment { start: 12, end: 345 });
+    }
+
+    #[test]
+    fn test_parse_pairs() {
+        let (_, pairs) = parse_pairs("2-4,6-8\n2-3,4-5\n").unwrap();
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[1].second, Assignment { start: 4, end: 5 });
+    }
+}
@@ -0,0 +1,123 @@
+mod parser;
+pub use parser::*;
+
+use advent::Solution;
+
+
+/// Move `count` crates from stack `from` to stack `to` (both 1-indexed).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Move {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CrateMover {
+    /// Moves one crate at a time.
+    Model9000,
+    /// Picks up all the crates of a move at once and keeps their order.
+    Model9001,
+}
+
+impl CrateMover {
+    pub fn apply(&self, stacks: &mut [Vec<char>], mv: &Move) {
+        let source = &mut stacks[mv.from - 1];
+        let mut lifted = source.split_off(source.len() - mv.count);
+        if *self == CrateMover::Model9000 {
+            lifted.reverse();
+        }
+        stacks[mv.to - 1].extend(lifted);
+    }
+}
+
+
+/// The starting stacks and the rearrangement procedure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Supplies {
+    pub stacks: Vec<Vec<char>>,
+    pub moves: Vec<Move>,
+}
+
+impl Supplies {
+    pub fn rearrange(&self, crane: CrateMover) -> Vec<Vec<char>> {
+        let mut stacks = self.stacks.clone();
+        for mv in &self.moves {
+            crane.apply(&mut stacks, mv);
+        }
+        stacks
+    }
+
+    /// The crate that ends up on top of each stack.
+    pub fn top_crates(&self, crane: CrateMover) -> String {
+        self.rearrange(crane)
+            .iter()
+            .filter_map(|stack| stack.last())
+            .collect()
+    }
+}
+
+
+pub fn get_supplies(s: &str) -> Supplies {
+    // Only trim the end: leading spaces are part of the drawing.
+    parse_supplies(s.trim_end()).unwrap().1
+}
+
+pub fn solve_part1(s: &str) -> String {
+    get_supplies(s).top_crates(CrateMover::Model9000)
+}
+
+pub fn solve_part2(s: &str) -> String {
+    get_supplies(s).top_crates(CrateMover::Model9001)
+}
+
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Supplies;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(&self, input: &str) -> Self::Input {
+        get_supplies(input)
+    }
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        input.top_crates(CrateMover::Model9000)
+    }
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        input.top_crates(CrateMover::Model9001)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../../../aoc/pkg/year_2022/testdata/day-05/input.txt");
+    const ANSWERS: &str = include_str!("../../../aoc/pkg/year_2022/testdata/day-05/answers.txt");
+
+    #[test]
+    fn smol() {
+        let s = "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+";
+        assert_eq!(solve_part1(s), "CMZ");
+        assert_eq!(solve_part2(s), "MCD");
+    }
+
+    #[test]
+    fn matches_go_fixture() {
+        let answers = ANSWERS.lines().collect::<Vec<_>>();
+        assert_eq!(solve_part1(FIXTURE), answers[0]);
+        assert_eq!(solve_part2(FIXTURE), answers[1]);
+    }
+}
@@ -0,0 +1,103 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::*,
+    combinator::map,
+    multi::{many1, separated_list1},
+    sequence::{delimited, preceded, terminated, tuple},
+    IResult,
+};
+
+use crate::{Move, Supplies};
+
+/// "[Z]" or, where the stack doesn't reach this high, "   ".
+fn parse_crate(s: &str) -> IResult<&str, Option<char>> {
+    alt((
+        map(delimited(char('['), satisfy(|c| c.is_ascii_alphabetic()), char(']')), Some),
+        map(tag("   "), |_| None),
+    ))(s)
+}
+
+pub fn parse_crate_row(s: &str) -> IResult<&str, Vec<Option<char>>> {
+    separated_list1(char(' '), parse_crate)(s)
+}
+
+/// " 1   2   3 "
+fn parse_stack_labels(s: &str) -> IResult<&str, Vec<u64>> {
+    delimited(space0, separated_list1(space1, u64), space0)(s)
+}
+
+/// The drawing of the stacks, bottom crate first in every stack.
+pub fn parse_stacks(s: &str) -> IResult<&str, Vec<Vec<char>>> {
+    let (s, rows) = many1(terminated(parse_crate_row, line_ending))(s)?;
+    let (s, labels) = parse_stack_labels(s)?;
+
+    let mut stacks = vec![vec![]; labels.len()];
+    for row in rows.iter().rev() {
+        for (stack, item) in stacks.iter_mut().zip(row) {
+            if let Some(item) = item {
+                stack.push(*item);
+            }
+        }
+    }
+    Ok((s, stacks))
+}
+
+/// "move 1 from 2 to 1"
+pub fn parse_move(s: &str) -> IResult<&str, Move> {
+    map(
+        tuple((
+            preceded(tag("move "), u64),
+            preceded(tag(" from "), u64),
+            preceded(tag(" to "), u64),
+        )),
+        |(count, from, to)| Move {
+            count: count as usize,
+            from: from as usize,
+            to: to as usize,
+        },
+    )(s)
+}
+
+pub fn parse_supplies(s: &str) -> IResult<&str, Supplies> {
+    map(
+        tuple((
+            terminated(parse_stacks, many1(line_ending)),
+            separated_list1(line_ending, parse_move),
+        )),
+        |(stacks, moves)| Supplies { stacks, moves },
+    )(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_crate_row() {
+        let (_, row) = parse_crate_row("    [D]    ").unwrap();
+        assert_eq!(row, vec![None, Some('D'), None]);
+        let (_, row) = parse_crate_row("[Z] [M] [P]").unwrap();
+        assert_eq!(row, vec![Some('Z'), Some('M'), Some('P')]);
+    }
+
+    #[test]
+    fn test_parse_stacks() {
+        let s = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n";
+        let (_, stacks) = parse_stacks(s).unwrap();
+        assert_eq!(stacks, vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]);
+    }
+
+    #[test]
+    fn test_parse_stacks_without_padding() {
+        let s = "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n";
+        let (_, stacks) = parse_stacks(s).unwrap();
+        assert_eq!(stacks, vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]);
+    }
+
+    #[test]
+    fn test_parse_move() {
+        let (_, mv) = parse_move("move 13 from 2 to 1").unwrap();
+        assert_eq!(mv, Move { count: 13, from: 2, to: 1 });
+    }
+}
@@ -19,7 +19,6 @@ pub struct File {
 impl Hash for File {
     fn hash<H: std::hash::Hasher>(&self, hasher: &mut H) {
         hasher.write(self.name.as_bytes());
-        hasher.finish();
     }
 }
 
@@ -32,7 +31,7 @@ pub enum FileLike {
 
 
 impl FileLike {
-    pub fn from_str(s: &str) -> IResult<&str, Self> {
+    pub fn parse(s: &str) -> IResult<&str, Self> {
         alt((
             map(
                 context("directory", preceded(tag("dir "), cut(alpha1))),
@@ -60,7 +59,7 @@ pub enum Operation {
 }
 
 impl Operation {
-    pub fn from_str(s: &str) -> IResult<&str, Self> {
+    pub fn parse(s: &str) -> IResult<&str, Self> {
         let (input, _) = tag("$ ")(s)?;
         alt((
             map(
@@ -77,7 +76,7 @@ impl<'a> TryFrom<&'a str> for Operation {
     type Error = nom::Err<nom::error::Error<&'a str>>;
 
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        Operation::from_str(value).map(|(_, op)| op)
+        Operation::parse(value).map(|(_, op)| op)
     }
 }
 
@@ -86,7 +85,7 @@ impl<'a> TryFrom<&'a str> for FileLike {
     type Error = nom::Err<nom::error::Error<&'a str>>;
 
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        FileLike::from_str(value).map(|(_, fl)| fl)
+        FileLike::parse(value).map(|(_, fl)| fl)
     }
 }
 
@@ -101,7 +100,7 @@ mod tests {
     #[test_case("$ cd ddgtnw", Operation::Cd("ddgtnw".to_owned()) ; "Change directory to some alphanumeric.")]
     #[test_case("$ ls", Operation::Ls ; "Ls")]
     fn parse_operation(text: &str, operation: Operation) {
-        let result = Operation::from_str(text);
+        let result = Operation::parse(text);
         assert!(result.is_ok());
         assert_eq!(result.unwrap().1, operation);
     }
@@ -115,7 +114,7 @@ mod tests {
     #[test_case("dir ddgtnw", FileLike::Dir("ddgtnw".to_owned()) ; "Dir check")]
     #[test_case("57336 tbq.wvz", FileLike::File(File { name: "tbq.wvz".to_owned(), size: 57336 }) ; "File check")]
     fn parse_filelike(text: &str, file_like: FileLike) {
-        let result = FileLike::from_str(text);
+        let result = FileLike::parse(text);
         let result = result.unwrap();
         // assert!(result.is_ok());
         assert_eq!(result.1, file_like);
@@ -1,6 +1,3 @@
-use crate::FileLike;
-use crate::File;
-
 pub struct Tree {
     pub root: Node
 }
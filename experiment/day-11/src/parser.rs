@@ -152,9 +152,9 @@ fn parse_operation_str(s: &str) -> IResult<&str, Operation> {
     Ok((rem, op))
 }
 
-fn parse_prefix_then_number<'a, 'b, T: FromStr>(
+fn parse_prefix_then_number<'a, T: FromStr>(
     s: &'a str,
-    prefix: &'b str,
+    prefix: &str,
 ) -> IResult<&'a str, T> {
     preceded(
         tag(prefix),
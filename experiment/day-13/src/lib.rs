@@ -1,17 +1,18 @@
 mod parse;
+use advent::Solution;
 use itertools::EitherOrBoth::{Both, Left, Right};
 use itertools::Itertools;
 use std::cmp::Ordering;
 
 pub use parse::*;
 
-#[derive(Debug, PartialEq, Eq, Clone, Ord)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Packet {
     One(usize),
     Many(Box<List>),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Ord)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct List {
     pub list: Vec<Packet>,
 }
@@ -47,56 +48,150 @@ impl<'a> IntoIterator for &'a List {
 }
 
 impl PartialOrd for List {
-    fn partial_cmp(&self, _other: &List) -> Option<Ordering> {
-        unreachable!(
-            "Just a no-op to make the compiler happy. It won't let me Ord without PartialOrd for List but the actual PartialOrd for Packet doesn't really use the List's PartialOrd anyways."
-        );
+    fn partial_cmp(&self, other: &List) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for List {
+    fn cmp(&self, other: &List) -> Ordering {
+        let mut conclusion = Ordering::Equal;
+
+        self.into_iter().zip_longest(other).for_each(|pair| {
+            if conclusion != Ordering::Equal {
+                return;
+            }
+            match pair {
+                Both(left, right) => {
+                    // Both left and right items are present and we haven't yet concluded anything.
+                    // Compare them.
+                    conclusion = left.cmp(right);
+                }
+                Left(_) => {
+                    // Right ran out of items.
+                    conclusion = Ordering::Greater;
+                }
+                Right(_) => {
+                    // Left ran out of items.
+                    conclusion = Ordering::Less;
+                }
+            };
+        });
+        conclusion
     }
 }
 
 impl PartialOrd for Packet {
     fn partial_cmp(&self, other: &Packet) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Packet) -> Ordering {
         match (self, other) {
-            (Packet::One(a), Packet::One(b)) => a.partial_cmp(b),
-            (Packet::One(a), m) if matches!(m, Packet::Many(_)) => Packet::Many(Box::new(List {
+            (Packet::One(a), Packet::One(b)) => a.cmp(b),
+            (Packet::One(a), Packet::Many(b)) => List {
                 list: vec![Packet::One(*a)],
-            }))
-            .partial_cmp(m),
-            (m, Packet::One(a)) if matches!(m, Packet::Many(_)) => {
-                m.partial_cmp(&Packet::Many(Box::new(List {
-                    list: vec![Packet::One(*a)],
-                })))
-            }
-            (Packet::Many(a), Packet::Many(b)) => {
-                let mut conclusion = None;
-
-                a.into_iter().zip_longest(b.into_iter()).for_each(|pair| {
-                    if conclusion.is_some() && conclusion != Some(Ordering::Equal) {
-                        return;
-                    }
-                    match pair {
-                        Both(left, right) => {
-                            // Both left and right items are present and we haven't yet concluded anything.
-                            // Compare them.
-                            conclusion = left.partial_cmp(right);
-                        }
-                        Left(_) => {
-                            // Right ran out of items.
-                            conclusion = Some(Ordering::Greater);
-                        }
-                        Right(_) => {
-                            // Left ran out of items.
-                            conclusion = Some(Ordering::Less);
-                        }
-                    };
-                });
-                conclusion
             }
-            _ => unreachable!("Happy now, rustc?"),
+            .cmp(b),
+            (Packet::Many(a), Packet::One(b)) => (**a).cmp(&List {
+                list: vec![Packet::One(*b)],
+            }),
+            (Packet::Many(a), Packet::Many(b)) => a.cmp(b),
         }
     }
 }
 
+
+pub fn parse_pairs(s: &str) -> Vec<(Packet, Packet)> {
+    s.split("\n\n")
+        .map(|pair| {
+            let item = pair.split('\n').collect::<Vec<&str>>();
+            let first = Packet::parse(item[0]).unwrap().1;
+            let second = Packet::parse(item[1]).unwrap().1;
+            (first, second)
+        })
+        .collect()
+}
+
+pub fn solve_part1(s: &str) -> usize {
+    sum_ordered_indices(&parse_pairs(s))
+}
+
+pub fn solve_part2(s: &str) -> usize {
+    decoder_key(&parse_pairs(s))
+}
+
+/// The sum of the (1-based) indices of the pairs that are in the right order.
+pub fn sum_ordered_indices(pairs: &[(Packet, Packet)]) -> usize {
+    pairs
+        .iter()
+        .enumerate()
+        .map(|(idx, (first, second))| {
+            if first.partial_cmp(second) == Some(Ordering::Less) {
+                idx + 1
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+/// Sort every packet along with the two divider packets
+/// and multiply the (1-based) indices of the dividers.
+pub fn decoder_key(pairs: &[(Packet, Packet)]) -> usize {
+    let mut pairs = pairs
+        .iter()
+        .flat_map(|(first, second)| [first.clone(), second.clone()])
+        .collect::<Vec<Packet>>();
+
+    let divider_packet1 = Packet::parse("[[2]]").unwrap().1;
+    let divider_packet2 = Packet::parse("[[6]]").unwrap().1;
+
+    pairs.push(divider_packet1.clone());
+    pairs.push(divider_packet2.clone());
+
+    pairs.sort();
+
+    let divider_packet1_index = pairs
+        .iter()
+        .enumerate()
+        .filter(|(_, packet)| packet == &&divider_packet1)
+        .map(|(idx, _)| idx)
+        .next()
+        .unwrap();
+
+    let divider_packet2_index = pairs
+        .iter()
+        .enumerate()
+        .filter(|(_, packet)| packet == &&divider_packet2)
+        .map(|(idx, _)| idx)
+        .next()
+        .unwrap();
+
+    (divider_packet1_index + 1) * (divider_packet2_index + 1)
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<(Packet, Packet)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Self::Input {
+        parse_pairs(input.trim())
+    }
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        sum_ordered_indices(input)
+    }
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        decoder_key(input)
+    }
+}
+
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,4 +216,35 @@ mod tests {
         let (_, p2) = Packet::parse(b).unwrap();
         assert_eq!(p1.partial_cmp(&p2), expected_ordering);
     }
+
+
+    #[test]
+    fn test_solution() {
+        let s = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]";
+
+        assert_eq!(solve_part1(s), 13);
+        assert_eq!(solve_part2(s), 140);
+    }
 }
@@ -0,0 +1,177 @@
+use std::fmt::Display;
+
+use crate::{Cave, Point, SOURCE};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cell {
+    Air,
+    Rock,
+    Sand,
+}
+
+/// The cave as one flat `Vec`, just big enough for everywhere the sand can get to.
+///
+/// Without a floor that's the rocks plus a column on either side, and anything
+/// leaving it falls into the abyss. With a floor, sand can spread at most one column
+/// per row it falls, so the triangle under the source is all it'll ever need.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    left: isize,
+    width: usize,
+    height: usize,
+    floor: bool,
+    cells: Vec<Cell>,
+    /// Where the last grain went on its way down, so the next one can pick up from there.
+    path: Vec<Point>,
+    sand: usize,
+}
+
+impl Grid {
+    pub fn new(cave: &Cave) -> Self {
+        let bottom = cave.rocks().map(|p| p.y).max().unwrap_or(0);
+        let (left, right, height) = match cave.include_bottom_floor {
+            true => {
+                let floor = bottom + 2;
+                (SOURCE.x - floor, SOURCE.x + floor, floor as usize)
+            },
+            false => {
+                let left = cave.rocks().map(|p| p.x).min().unwrap_or(SOURCE.x).min(SOURCE.x) - 1;
+                let right = cave.rocks().map(|p| p.x).max().unwrap_or(SOURCE.x).max(SOURCE.x) + 1;
+                (left, right, bottom as usize + 1)
+            },
+        };
+
+        let width = (right - left + 1) as usize;
+        let mut grid = Self {
+            left,
+            width,
+            height,
+            floor: cave.include_bottom_floor,
+            cells: vec![Cell::Air; width * height],
+            path: vec![SOURCE],
+            sand: 0,
+        };
+        for rock in cave.rocks() {
+            if let Some(index) = grid.index(rock) {
+                grid.cells[index] = Cell::Rock;
+            }
+        }
+        for &sand in &cave.sand {
+            if let Some(index) = grid.index(sand) {
+                grid.cells[index] = Cell::Sand;
+            }
+        }
+        grid
+    }
+
+    fn index(&self, point: Point) -> Option<usize> {
+        let x = point.x - self.left;
+        let in_bounds = (0..self.width as isize).contains(&x) && (0..self.height as isize).contains(&point.y);
+        in_bounds.then(|| point.y as usize * self.width + x as usize)
+    }
+
+    /// What's at `point`, or `None` if it's out in the abyss.
+    pub fn get(&self, point: Point) -> Option<Cell> {
+        if self.floor && point.y == self.height as isize {
+            return Some(Cell::Rock);
+        }
+        self.index(point).map(|index| self.cells[index])
+    }
+
+    /// How many grains have come to rest so far.
+    pub fn sand(&self) -> usize {
+        self.sand
+    }
+
+    /// Let the next grain fall from wherever the last one was just before it came to rest.
+    /// Returns where it settles, or `None` once sand falls into the abyss or the source is covered.
+    pub fn drop_grain(&mut self) -> Option<Point> {
+        loop {
+            let &position = self.path.last()?;
+            let below = [0, -1, 1].map(|dx| Point { x: position.x + dx, y: position.y + 1 });
+
+            match below.iter().find(|&&next| matches!(self.get(next), None | Some(Cell::Air))) {
+                Some(&next) if self.get(next).is_none() => return None,
+                Some(&next) => self.path.push(next),
+                None => {
+                    self.path.pop();
+                    let index = self.index(position).expect("Sand only rests inside the grid.");
+                    self.cells[index] = Cell::Sand;
+                    self.sand += 1;
+                    return Some(position);
+                },
+            }
+        }
+    }
+
+    /// Keep pouring until no more sand comes to rest, and count how much did.
+    pub fn pour(&mut self) -> usize {
+        while self.drop_grain().is_some() {}
+        self.sand
+    }
+}
+
+impl Display for Grid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.cells.chunks(self.width) {
+            let line = row
+                .iter()
+                .map(|cell| match cell {
+                    Cell::Air => '.',
+                    Cell::Rock => '#',
+                    Cell::Sand => 'o',
+                })
+                .collect::<String>();
+            writeln!(f, "{}", line)?;
+        }
+        if self.floor {
+            writeln!(f, "{}", "#".repeat(self.width))?;
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parse;
+
+    const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+
+    #[test]
+    fn settles_like_the_puzzle() {
+        let (_, cave) = Cave::parse(EXAMPLE).unwrap();
+        let mut grid = Grid::new(&cave);
+        assert_eq!(grid.drop_grain(), Some(Point { x: 500, y: 8 }));
+        assert_eq!(grid.drop_grain(), Some(Point { x: 499, y: 8 }));
+        assert_eq!(grid.pour(), 24);
+        assert_eq!(grid.drop_grain(), None);
+        assert_eq!(grid.to_string(), "............
+............
+.......o....
+......ooo...
+.....#ooo##.
+....o#ooo#..
+...###ooo#..
+.....oooo#..
+..o.ooooo#..
+.#########..
+");
+    }
+
+    #[test]
+    fn floor_needs_no_bounds() {
+        let (_, mut cave) = Cave::parse(EXAMPLE).unwrap();
+        cave.include_bottom_floor = true;
+        let mut grid = Grid::new(&cave);
+        // The floor is at y = 11, so sand spreads at most 11 either side of the source.
+        assert_eq!(grid.get(Point { x: 489, y: 10 }), Some(Cell::Air));
+        assert_eq!(grid.get(Point { x: 488, y: 10 }), None);
+        assert_eq!(grid.get(Point { x: 0, y: 11 }), Some(Cell::Rock));
+        assert_eq!(grid.pour(), 93);
+        assert_eq!(grid.get(SOURCE), Some(Cell::Sand));
+    }
+}
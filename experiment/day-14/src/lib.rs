@@ -1,8 +1,15 @@
+mod grid;
 mod parse;
+#[cfg(test)]
+mod test_support;
+mod triangle;
+use advent::Solution;
 use std::collections::HashSet;
 use std::fmt::Display;
 
+pub use grid::*;
 pub use parse::*;
+pub use triangle::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Point {
@@ -10,6 +17,9 @@ pub struct Point {
     pub y: isize,
 }
 
+/// Where the sand comes from.
+pub const SOURCE: Point = Point { x: 500, y: 0 };
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct RockSegment {
     pub points: Vec<Point>,
@@ -30,8 +40,7 @@ impl RockSegment {
                     } else {
                         buffer.extend(
                             (end.y..=start.y)
-                                .map(|y| Point { x: start.x, y })
-                                .into_iter(),
+                                .map(|y| Point { x: start.x, y }),
                         );
                     }
                 }
@@ -39,14 +48,12 @@ impl RockSegment {
                     if start.x < end.x {
                         buffer.extend(
                             (start.x..=end.x)
-                                .map(|x| Point { x, y: start.y })
-                                .into_iter(),
+                                .map(|x| Point { x, y: start.y }),
                         );
                     } else {
                         buffer.extend(
                             (end.x..=start.x)
-                                .map(|x| Point { x, y: start.y })
-                                .into_iter(),
+                                .map(|x| Point { x, y: start.y }),
                         );
                     }
                 }
@@ -124,8 +131,6 @@ pub struct Cave {
     pub rock_segments: Vec<RockSegment>,
     pub sand: Vec<Point>,
     pub include_bottom_floor: bool,
-    pub floor_left_most: Option<isize>,
-    pub floor_right_most: Option<isize>,
 }
 
 impl Cave {
@@ -135,28 +140,24 @@ impl Cave {
             .flat_map(|segment| segment.rocks())
     }
 
+    /// The floor is infinite, but sand spreads at most one column for every row
+    /// it falls, so only the part under the triangle below the source matters.
     pub fn bottom_floor(&self) -> impl Iterator<Item = Point> + '_ {
-        let y = self.bottom_right().y + 2;
-        // let left_most = self.rocks().map(|p| p.x).min().unwrap();
-        // let right_most = self.rocks().map(|p| p.x).max().unwrap();
-
-        (self.floor_left_most.unwrap()..=self.floor_right_most.unwrap())
-            .map(move |x| Point { x, y })
+        let y = self.rocks().map(|p| p.y).max().unwrap() + 2;
+        (SOURCE.x - y..=SOURCE.x + y).map(move |x| Point { x, y })
     }
 
     pub fn top_left(&self) -> Point {
         let mut left_most = self.rocks().map(|p| p.x).min().unwrap();
         if self.include_bottom_floor {
-            // Now the left and right are virtually infinite.
-            left_most = 450;
+            left_most = left_most.min(self.bottom_floor().next().unwrap().x);
         }
         Point { x: left_most, y: 0 }
     }
     pub fn bottom_right(&self) -> Point {
         let mut right_most = self.rocks().map(|p| p.x).max().unwrap();
         if self.include_bottom_floor {
-            // Now the left and right are virtually infinite.
-            right_most = 550;
+            right_most = right_most.max(self.bottom_floor().last().unwrap().x);
         }
         let bottom_most = self.rocks().map(|p| p.y).max().unwrap();
         Point {
@@ -176,7 +177,7 @@ impl Cave {
 
         Trajectory {
             obstacles,
-            position: Point { x: 500, y: 0 },
+            position: SOURCE,
             current_streak: 0,
         }
     }
@@ -213,3 +214,117 @@ impl Display for Cave {
         writeln!(f)
     }
 }
+
+
+pub fn solve_part1(s: &str) -> usize {
+    let cave = Cave::parse(s).unwrap().1;
+    Grid::new(&cave).pour()
+}
+
+pub fn solve_part2(s: &str) -> usize {
+    pour_onto_floor(&Cave::parse(s).unwrap().1)
+}
+
+/// Pour sand into the cave with the floor under its lowest rock put in.
+pub fn pour_onto_floor(cave: &Cave) -> usize {
+    let mut cave = cave.clone();
+    cave.include_bottom_floor = true;
+    Grid::new(&cave).pour()
+}
+
+/// Part 2 without pouring any sand, by counting what's left of the triangle under the source.
+pub fn solve_part2_triangle(s: &str) -> usize {
+    let cave = Cave::parse(s).unwrap().1;
+    count_reachable(&cave)
+}
+
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Cave;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Self::Input {
+        Cave::parse(input.trim()).unwrap().1
+    }
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        Grid::new(input).pour()
+    }
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        pour_onto_floor(input)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_support::{render, segment};
+    use advent::rng::Rng;
+
+    #[test]
+    fn test_smol() {
+        let s = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+        assert_eq!(solve_part1(s), 24);
+        assert_eq!(solve_part2(s), 93);
+    }
+
+    #[test]
+    fn test_big() {
+        let s = include_str!("input.txt");
+
+        assert_eq!(solve_part1(s), 638);
+        assert_eq!(solve_part2(s), 31_722);
+        assert_eq!(solve_part2_triangle(s), 31_722);
+    }
+
+    /// Pour the way it used to be done: a fresh [`Trajectory`] from the source for every grain.
+    fn pour_by_trajectory(mut cave: Cave) -> usize {
+        loop {
+            let mut trajectory = cave.get_trajectory();
+            let mut steps_taken = 0;
+            for (_, current_streak) in trajectory.by_ref() {
+                steps_taken += 1;
+                if current_streak > 1_000 {
+                    return cave.sand.len();
+                }
+            }
+            cave.stabilize_sand(trajectory.position);
+            if steps_taken == 0 {
+                return cave.sand.len();
+            }
+        }
+    }
+
+    /// Both ways of pouring on the same cave, without the floor and then with it.
+    fn pour_both_ways(s: &str) -> [(usize, usize); 2] {
+        let mut cave = Cave::parse(s).unwrap().1;
+        [false, true].map(|floor| {
+            cave.include_bottom_floor = floor;
+            (Grid::new(&cave).pour(), pour_by_trajectory(cave.clone()))
+        })
+    }
+
+    #[test]
+    fn grid_agrees_with_trajectories() {
+        let s = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+        assert_eq!(pour_both_ways(s), [(24, 24), (93, 93)]);
+        let mut cave = Cave::parse(s).unwrap().1;
+        cave.include_bottom_floor = true;
+        assert_eq!(cave.bottom_floor().count(), 2 * 11 + 1);
+
+        let mut rng = Rng::new(24);
+        for _ in 0..50 {
+            let segments = (0..rng.range(1..=8)).map(|_| segment(&mut rng)).collect::<Vec<_>>();
+            let s = render(&segments);
+            for (grid, trajectory) in pour_both_ways(&s) {
+                assert_eq!(grid, trajectory, "{}", s);
+            }
+        }
+    }
+}
@@ -54,8 +54,6 @@ impl Parse for Cave {
                 rock_segments,
                 sand: vec![],
                 include_bottom_floor: false,
-                floor_left_most: None,
-                floor_right_most: None,
             },
         )(s)
     }
@@ -0,0 +1,35 @@
+//! Fixtures shared by the tests of more than one module.
+
+use crate::{Point, RockSegment};
+use advent::rng::Rng;
+
+/// A path of a few horizontal and vertical lines somewhere under the source.
+pub fn segment(rng: &mut Rng) -> RockSegment {
+    let mut point = Point { x: rng.range(485..=514), y: rng.range(1..=20) };
+    let mut points = vec![point];
+    for _ in 0..rng.range(1..=3) {
+        let length = rng.range(-3..=3);
+        point = match rng.range(0..=1) {
+            0 => Point { x: point.x + length, y: point.y },
+            _ => Point { x: point.x, y: (point.y + length).max(1) },
+        };
+        if point != *points.last().unwrap() {
+            points.push(point);
+        }
+    }
+    if points.len() == 1 {
+        points.push(Point { x: point.x + 1, y: point.y });
+    }
+    RockSegment { points }
+}
+
+/// The segments written out the way the puzzle input has them, one path per line.
+pub fn render(segments: &[RockSegment]) -> String {
+    segments
+        .iter()
+        .map(|segment| {
+            segment.points.iter().map(|p| format!("{},{}", p.x, p.y)).collect::<Vec<_>>().join(" -> ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,63 @@
+use crate::{Cave, SOURCE};
+
+
+/// With a floor in place, sand ends up everywhere in the triangle under the source
+/// except where rock is, and wherever rock casts a shadow: a cell that nothing
+/// directly above or diagonally above can get sand into stays empty too.
+/// So walk down the triangle a row at a time, without dropping a single grain.
+pub fn count_reachable(cave: &Cave) -> usize {
+    let floor = cave.rocks().map(|p| p.y).max().unwrap_or(0) + 2;
+    let left = SOURCE.x - floor;
+    let width = (2 * floor + 1) as usize;
+
+    let mut rocks = vec![vec![false; width]; floor as usize];
+    for rock in cave.rocks() {
+        let x = rock.x - left;
+        if (0..floor).contains(&rock.y) && (0..width as isize).contains(&x) {
+            rocks[rock.y as usize][x as usize] = true;
+        }
+    }
+
+    let source = (SOURCE.x - left) as usize;
+    let mut reachable = vec![false; width];
+    reachable[source] = !rocks[0][source];
+    let mut count = reachable[source] as usize;
+
+    for (y, row) in rocks.iter().enumerate().skip(1) {
+        // Row y only reaches y columns either side of the source.
+        let (start, end) = (source - y, source + y);
+        let mut next = vec![false; width];
+        for x in start..=end {
+            let parents = x.saturating_sub(1)..=(x + 1).min(width - 1);
+            next[x] = !row[x] && reachable[parents].iter().any(|&parent| parent);
+        }
+        count += next.iter().filter(|&&cell| cell).count();
+        reachable = next;
+    }
+    count
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_part2, solve_part2_triangle, Parse};
+    use crate::test_support::{render, segment};
+    use advent::rng::Rng;
+
+    #[test]
+    fn example() {
+        let (_, cave) = Cave::parse("498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9").unwrap();
+        assert_eq!(count_reachable(&cave), 93);
+    }
+
+    #[test]
+    fn agrees_with_pouring_on_random_caves() {
+        let mut rng = Rng::new(14);
+        for _ in 0..200 {
+            let segments = (0..rng.range(1..=8)).map(|_| segment(&mut rng)).collect::<Vec<_>>();
+            let s = render(&segments);
+            assert_eq!(solve_part2_triangle(&s), solve_part2(&s), "{}", s);
+        }
+    }
+}
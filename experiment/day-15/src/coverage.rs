@@ -0,0 +1,108 @@
+use std::ops::RangeInclusive;
+
+use crate::{ClosestBeaconMap, Position};
+
+
+/// Sort the intervals and merge the ones that overlap or touch.
+pub fn merge_intervals(mut intervals: Vec<RangeInclusive<isize>>) -> Vec<RangeInclusive<isize>> {
+    intervals.sort_by_key(|interval| *interval.start());
+
+    let mut merged: Vec<RangeInclusive<isize>> = Vec::with_capacity(intervals.len());
+    for interval in intervals {
+        match merged.last_mut() {
+            Some(last) if *interval.start() <= *last.end() + 1 => {
+                if interval.end() > last.end() {
+                    *last = *last.start()..=*interval.end();
+                }
+            },
+            _ => merged.push(interval),
+        }
+    }
+    merged
+}
+
+pub fn covered_length(intervals: &[RangeInclusive<isize>]) -> usize {
+    intervals
+        .iter()
+        .map(|interval| (interval.end() - interval.start() + 1) as usize)
+        .sum()
+}
+
+
+impl ClosestBeaconMap {
+    /// The stretches of `row` that some sensor can see, merged and in order.
+    pub fn coverage(&self, row: isize) -> Vec<RangeInclusive<isize>> {
+        let intervals = self
+            .sensor_to_beacon_map
+            .iter()
+            .filter_map(|(sensor, closest_beacon)| {
+                // Whatever reach the sensor has left after getting to the row.
+                let reach = sensor.distance(closest_beacon) - (sensor.y - row).abs();
+                (reach >= 0).then(|| sensor.x - reach..=sensor.x + reach)
+            })
+            .collect();
+        merge_intervals(intervals)
+    }
+
+    /// How many positions along `row` can't hold an undetected beacon:
+    /// everything the sensors see, except for the sensors and beacons themselves.
+    pub fn count_covered_along_row(&self, row: isize) -> usize {
+        let coverage = self.coverage(row);
+        let occupied = self
+            .occupied_positions
+            .iter()
+            .filter(|position| position.y == row)
+            .filter(|position| coverage.iter().any(|interval| interval.contains(&position.x)))
+            .count();
+        covered_length(&coverage) - occupied
+    }
+
+    /// The first position along `row` within `0..=upper_bound` that no sensor can see.
+    pub fn find_gap_along_row(&self, row: isize, upper_bound: isize) -> Option<Position> {
+        let mut x = 0;
+        for interval in self.coverage(row) {
+            if *interval.start() > x {
+                break;
+            }
+            x = x.max(interval.end() + 1);
+        }
+        (x <= upper_bound).then_some(Position { x, y: row })
+    }
+}
+
+
+/// The other way to find the distress beacon: sweep every row
+/// until one of them has a gap in its coverage.
+pub fn find_distress_beacon_by_sweep(
+    sensor_to_beacon_map: &ClosestBeaconMap,
+    upper_bound: isize,
+) -> Option<Position> {
+    (0..=upper_bound).find_map(|row| sensor_to_beacon_map.find_gap_along_row(row, upper_bound))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parse;
+
+    #[test]
+    fn merges_overlapping_and_touching() {
+        let merged = merge_intervals(vec![5..=7, -2..=1, 0..=3, 4..=4, 10..=12, 11..=11]);
+        assert_eq!(merged, vec![-2..=7, 10..=12]);
+        assert_eq!(covered_length(&merged), 10 + 3);
+        assert_eq!(merge_intervals(vec![]), vec![]);
+    }
+
+    #[test]
+    fn covers_a_row_of_one_sensor() {
+        let (_, map) = ClosestBeaconMap::parse("Sensor at x=8, y=7: closest beacon is at x=2, y=10").unwrap();
+        assert_eq!(map.coverage(10), vec![2..=14]);
+        assert_eq!(map.coverage(16), vec![8..=8]);
+        assert_eq!(map.coverage(17), vec![]);
+        // The beacon at (2, 10) is the one position on the row that isn't ruled out.
+        assert_eq!(map.count_covered_along_row(10), 12);
+        assert_eq!(map.find_gap_along_row(10, 20), Some(Position { x: 0, y: 10 }));
+        assert_eq!(map.find_gap_along_row(7, 16), None);
+    }
+}
@@ -0,0 +1,234 @@
+//! Manhattan geometry in rotated coordinates, `u = x + y` and `v = x - y`.
+//!
+//! Turned by 45°, a sensor's diamond becomes an axis-aligned square and its edges
+//! become lines of constant `u` or `v`, so everything is just comparing integers.
+//! The one catch is that only points with `u` and `v` of the same parity
+//! come from a whole-numbered `(x, y)`.
+
+use std::ops::RangeInclusive;
+
+use crate::Position;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Rotated {
+    pub u: isize,
+    pub v: isize,
+}
+
+impl From<Position> for Rotated {
+    fn from(position: Position) -> Self {
+        Rotated { u: position.x + position.y, v: position.x - position.y }
+    }
+}
+
+impl Rotated {
+    /// The grid position, unless this is halfway between grid positions.
+    pub fn to_position(&self) -> Option<Position> {
+        let (sum, difference) = (self.u + self.v, self.u - self.v);
+        (sum.rem_euclid(2) == 0).then_some(Position { x: sum / 2, y: difference / 2 })
+    }
+}
+
+
+/// A diagonal line through the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagonalLine {
+    /// `x + y = c`, going down to the left.
+    U(isize),
+    /// `x - y = c`, going down to the right.
+    V(isize),
+}
+
+impl DiagonalLine {
+    pub fn contains(&self, point: Rotated) -> bool {
+        match *self {
+            DiagonalLine::U(c) => point.u == c,
+            DiagonalLine::V(c) => point.v == c,
+        }
+    }
+
+    /// Where the lines cross, which may be halfway between grid positions.
+    /// `None` for parallel lines.
+    pub fn intersection(&self, other: &DiagonalLine) -> Option<Rotated> {
+        match (*self, *other) {
+            (DiagonalLine::U(u), DiagonalLine::V(v)) | (DiagonalLine::V(v), DiagonalLine::U(u)) => {
+                Some(Rotated { u, v })
+            },
+            _ => None,
+        }
+    }
+}
+
+
+/// An axis-aligned rectangle in rotated coordinates, bounds included.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub u: RangeInclusive<isize>,
+    pub v: RangeInclusive<isize>,
+}
+
+impl Rect {
+    pub fn is_empty(&self) -> bool {
+        self.u.is_empty() || self.v.is_empty()
+    }
+
+    pub fn contains(&self, point: Rotated) -> bool {
+        self.u.contains(&point.u) && self.v.contains(&point.v)
+    }
+
+    pub fn intersection(&self, other: &Rect) -> Rect {
+        Rect {
+            u: *self.u.start().max(other.u.start())..=*self.u.end().min(other.u.end()),
+            v: *self.v.start().max(other.v.start())..=*self.v.end().min(other.v.end()),
+        }
+    }
+
+    /// What's left of `self` after cutting `other` out of it, as at most four
+    /// disjoint rectangles: full-height strips on either side in `u`,
+    /// and whatever is above and below `other` in between.
+    pub fn difference(&self, other: &Rect) -> Vec<Rect> {
+        let overlap = self.intersection(other);
+        if overlap.is_empty() {
+            return vec![self.clone()];
+        }
+
+        let (u_start, u_end) = (*self.u.start(), *self.u.end());
+        let (v_start, v_end) = (*self.v.start(), *self.v.end());
+        let (cut_u_start, cut_u_end) = (*overlap.u.start(), *overlap.u.end());
+        let (cut_v_start, cut_v_end) = (*overlap.v.start(), *overlap.v.end());
+
+        [
+            Rect { u: u_start..=cut_u_start - 1, v: v_start..=v_end },
+            Rect { u: cut_u_end + 1..=u_end, v: v_start..=v_end },
+            Rect { u: cut_u_start..=cut_u_end, v: v_start..=cut_v_start - 1 },
+            Rect { u: cut_u_start..=cut_u_end, v: cut_v_end + 1..=v_end },
+        ]
+        .into_iter()
+        .filter(|rect| !rect.is_empty())
+        .collect()
+    }
+
+    /// Every grid position inside.
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        self.u
+            .clone()
+            .flat_map(move |u| self.v.clone().map(move |v| Rotated { u, v }))
+            .filter_map(|point| point.to_position())
+    }
+}
+
+
+/// All the positions within `radius` of `center`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Diamond {
+    pub center: Position,
+    pub radius: isize,
+}
+
+impl Diamond {
+    pub fn new(center: Position, radius: isize) -> Self {
+        Self { center, radius }
+    }
+
+    pub fn rect(&self) -> Rect {
+        let Rotated { u, v } = self.center.into();
+        Rect {
+            u: u - self.radius..=u + self.radius,
+            v: v - self.radius..=v + self.radius,
+        }
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        self.rect().contains(position.into())
+    }
+
+    /// The positions in `self` that aren't in `other`.
+    pub fn difference(&self, other: &Diamond) -> Vec<Rect> {
+        self.rect().difference(&other.rect())
+    }
+
+    /// The four lines the edges lie on: `x + y` at its lowest and highest, then `x - y`.
+    pub fn edges(&self) -> [DiagonalLine; 4] {
+        let Rotated { u, v } = self.center.into();
+        [
+            DiagonalLine::U(u - self.radius),
+            DiagonalLine::U(u + self.radius),
+            DiagonalLine::V(v - self.radius),
+            DiagonalLine::V(v + self.radius),
+        ]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent::rng::Rng;
+
+    fn diamond(rng: &mut Rng) -> Diamond {
+        let center = Position { x: rng.range(-6..=6), y: rng.range(-6..=6) };
+        Diamond::new(center, rng.range(0..=5))
+    }
+
+    fn window() -> impl Iterator<Item = Position> {
+        (-15..=15).flat_map(|x| (-15..=15).map(move |y| Position { x, y }))
+    }
+
+    #[test]
+    fn round_trips_positions() {
+        for position in window() {
+            let rotated = Rotated::from(position);
+            assert_eq!(rotated.to_position(), Some(position));
+            assert_eq!(Rotated { u: rotated.u + 1, v: rotated.v }.to_position(), None);
+        }
+    }
+
+    #[test]
+    fn contains_matches_distance() {
+        let mut rng = Rng::new(15);
+        for _ in 0..200 {
+            let diamond = diamond(&mut rng);
+            for position in window() {
+                let inside = diamond.center.distance(&position) <= diamond.radius;
+                assert_eq!(diamond.contains(position), inside, "{:?} {:?}", diamond, position);
+            }
+        }
+    }
+
+    #[test]
+    fn difference_matches_brute_force() {
+        let mut rng = Rng::new(2022);
+        for _ in 0..200 {
+            let (a, b) = (diamond(&mut rng), diamond(&mut rng));
+            let mut expected = window()
+                .filter(|&position| a.contains(position) && !b.contains(position))
+                .collect::<Vec<_>>();
+            let mut observed = a.difference(&b).iter().flat_map(|rect| rect.positions().collect::<Vec<_>>()).collect::<Vec<_>>();
+            expected.sort();
+            observed.sort();
+            // The pieces don't overlap, so nothing shows up twice either.
+            assert_eq!(observed, expected, "{:?} minus {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn intersections_are_exact() {
+        let mut rng = Rng::new(7);
+        for _ in 0..200 {
+            let (a, b) = (diamond(&mut rng), diamond(&mut rng));
+            for edge in a.edges() {
+                for other in b.edges() {
+                    let on_both = window()
+                        .filter(|&position| edge.contains(position.into()) && other.contains(position.into()))
+                        .collect::<Vec<_>>();
+                    match edge.intersection(&other) {
+                        // Parallel lines are either the same line, or never meet.
+                        None => assert!(on_both.is_empty() || edge == other),
+                        Some(point) => assert_eq!(point.to_position().into_iter().collect::<Vec<_>>(), on_both),
+                    }
+                }
+            }
+        }
+    }
+}
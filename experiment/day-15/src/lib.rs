@@ -1,15 +1,21 @@
+mod coverage;
+mod geometry;
 mod parse;
+mod render;
+pub use coverage::*;
+pub use geometry::*;
+pub use render::*;
+use advent::Solution;
 use std::cmp::Ordering;
 use std::fmt::Display;
 use std::hash::Hash;
 pub use parse::*;
-use rayon::collections::vec_deque::Iter;
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 use std::ops::Deref;
 use std::collections::{HashMap, HashSet};
 
 
-#[derive(Clone, Copy, PartialEq, Eq, Ord)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Position {
     pub x: isize,
     pub y: isize
@@ -17,9 +23,15 @@ pub struct Position {
 
 impl PartialOrd for Position {
     fn partial_cmp(&self, other: &Position) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Position {
+    fn cmp(&self, other: &Position) -> Ordering {
         self.y
-        .partial_cmp(&other.y)
-        .or_else(|| self.x.partial_cmp(&other.x))
+        .cmp(&other.y)
+        .then_with(|| self.x.cmp(&other.x))
     }
 }
 
@@ -43,30 +55,6 @@ impl std::fmt::Debug for Position {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-pub struct Line {
-    pub gradient: isize,
-    pub y_intercept: isize,
-}
-
-
-impl Line {
-    pub fn new(start: Position, end: Position) -> Self {
-        let gradient = (end.y - start.y) / (end.x - start.x);
-        let y_intercept = start.y - gradient * start.x;
-        Self { gradient, y_intercept }
-    }
-    pub fn intersection_point(&self, other: &Self) -> Option<Position> {
-        if self.gradient == other.gradient {
-            return None;
-        }
-        let x = (other.y_intercept - self.y_intercept) / (self.gradient - other.gradient);
-        let y = self.gradient * x + self.y_intercept;
-        Some(Position { x, y })
-    }
-}
-
-
 pub type Sensor = Position;
 pub type Beacon = Position;
 
@@ -91,33 +79,27 @@ impl ClosestBeaconMap {
     }
 
 
-    pub fn get_boundary_lines(&self, sensor: &Sensor) -> [Line; 4] {
+    /// The diamond of positions this sensor can see.
+    pub fn get_diamond(&self, sensor: &Sensor) -> Diamond {
         let closest_beacon = self.sensor_to_beacon_map.get(sensor).unwrap();
-        let max_distance = sensor.distance(closest_beacon);
+        Diamond::new(*sensor, sensor.distance(closest_beacon))
+    }
 
-        [
-            Line::new(
-                Position { x: sensor.x, y: sensor.y + max_distance + 1 }, Position { x: sensor.x + max_distance + 1, y: sensor.y }
-            ),
-            Line::new(
-                Position { x: sensor.x, y: sensor.y + max_distance + 1 }, Position { x: sensor.x - max_distance - 1, y: sensor.y }
-            ),
-            Line::new(
-                Position { x: sensor.x - max_distance - 1, y: sensor.y }, Position { x: sensor.x, y: sensor.y - max_distance - 1 }
-            ),
-            Line::new(
-                Position { x: sensor.x + max_distance + 1, y: sensor.y }, Position { x: sensor.x, y: sensor.y - max_distance - 1 }
-            )
-        ]
+    /// The lines through the positions just out of the sensor's reach.
+    pub fn get_boundary_lines(&self, sensor: &Sensor) -> [DiagonalLine; 4] {
+        let diamond = self.get_diamond(sensor);
+        Diamond::new(diamond.center, diamond.radius + 1).edges()
     }
 
+    /// Where the boundary lines of the two sensors cross on the grid.
+    /// Crossings halfway between grid positions can't hold a beacon, so they're left out.
     pub fn get_intersection_points(&self, sensor1: &Sensor, sensor2: &Sensor) -> Vec<Position>{
         let lines = self.get_boundary_lines(sensor1);
         let other_lines = self.get_boundary_lines(sensor2);
         let mut intersection_points = Vec::new();
         for line in lines.iter() {
             for other_line in other_lines.iter() {
-                if let Some(point) = line.intersection_point(other_line) {
+                if let Some(point) = line.intersection(other_line).and_then(|point| point.to_position()) {
                     intersection_points.push(point);
                 }
             }
@@ -197,8 +179,8 @@ impl ClosestBeaconMap {
             //     !self.is_available(&Position { x: col, y: row })
             // })
             .map(move |col| {
-                let pos = Position { x: col, y: row };
-                pos
+                
+                Position { x: col, y: row }
             })
         })
         .collect::<HashSet<Position>>()
@@ -222,7 +204,7 @@ impl ClosestBeaconMap {
                 })
                 .collect::<HashSet<Position>>()
             },
-            false => return HashSet::new()
+            false => HashSet::new()
         }
     }
 
@@ -262,7 +244,7 @@ impl ClosestBeaconMap {
             self.get_available_along_row(lower_bound, upper_bound, row).into_iter()
         })
         .fold(
-            || HashSet::new(),
+            HashSet::new,
             |m1, m2| {
                 m2.fold(m1, |mut acc, p| {
                     acc.insert(p);
@@ -270,7 +252,7 @@ impl ClosestBeaconMap {
                 })
             })
             .reduce(
-            || HashSet::new(),
+            HashSet::new,
             |m1, m2| {
                 m2.iter().fold(m1, |mut acc, &v| {
                     acc.insert(v);
@@ -291,6 +273,100 @@ impl Hash for Sensor {
 }
 
 
+/// Idea:
+/// 
+/// For each sensor, obtain the leftmost and rightmost positions on the given row.
+/// Merge those intervals and add up their lengths, leaving out the sensors and beacons on the row.
+pub fn solve_part1(s: &str, row: isize) -> usize {
+    let (_, sensor_to_beacon_map) = ClosestBeaconMap::parse(s).unwrap();
+    sensor_to_beacon_map.count_covered_along_row(row)
+}
+
+/// Idea:
+/// 
+/// Since exactly one position exists where
+/// the distress beacon could be, it must lie pretty close outside the boundary
+/// of one of the sensors. If it lies a little too far, then there exists a position between the
+/// boundary and that point that is also a valid position for the beacon, but that cannot happen.
+/// 
+/// Now, we could naively check all points along the boundary, but there is a better way.
+/// Since the distress beacon must lie outside boundaries of all the sensors, in particular,
+/// it must lie just outside the intersection of some two sensor boundaries. We can obtain the
+/// points of intersection of boundaries of two sensors by solving a system of linear equations
+/// of four line segments with gradients +- 1. 
+/// 
+/// A pair of sensors may have at most 8 intersection points since every line on the boundary
+/// intersects exactly 2 other lines in the other sensor's boundary. For each of those 8 points,
+/// we check if it is a valid position for the beacon. If it is, we return it.
+/// 
+/// Therefore we end up checking O(|sensor| * |sensor| * 8) points and for each point we verify
+/// against every sensor that it lies outside the boundaries. Therefore this solution is O(|sensor|^3).
+/// 
+pub fn solve_part2(
+    s: &str,
+    upper_bound: isize,
+) -> Option<Position> {
+    let (_, sensor_to_beacon_map) = ClosestBeaconMap::parse(s).unwrap();
+    find_distress_beacon(&sensor_to_beacon_map, upper_bound)
+}
+
+/// Same as [`solve_part2`], but sweeping the rows for a gap in their coverage instead.
+pub fn solve_part2_sweep(
+    s: &str,
+    upper_bound: isize,
+) -> Option<Position> {
+    let (_, sensor_to_beacon_map) = ClosestBeaconMap::parse(s).unwrap();
+    find_distress_beacon_by_sweep(&sensor_to_beacon_map, upper_bound)
+}
+
+/// The search behind [`solve_part2`], on an already parsed map.
+pub fn find_distress_beacon(
+    sensor_to_beacon_map: &ClosestBeaconMap,
+    upper_bound: isize,
+) -> Option<Position> {
+    let sensors = sensor_to_beacon_map.keys().collect::<Vec<_>>();
+    let sensors_cp = sensors.clone();
+
+    sensors
+    .iter()
+    .flat_map(|&sensor| {
+        sensors_cp.iter().map(|&s| (*sensor, *s))
+    }).collect::<Vec<_>>()
+    .par_iter()
+    .find_map_first(|(sensor1, sensor2)| {
+        match sensor1 == sensor2 {
+            true => None,
+            false => {
+                sensor_to_beacon_map
+                .get_intersection_points(sensor1, sensor2)
+                .par_iter()
+                .find_first(|&pos| (0 <= pos.x && pos.x <= upper_bound && 0 <= pos.y && pos.y <= upper_bound) && sensor_to_beacon_map.is_free_position(pos)).copied()
+            }
+        }
+    })
+}
+
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = ClosestBeaconMap;
+    type Answer1 = usize;
+    type Answer2 = isize;
+
+    fn parse(&self, input: &str) -> Self::Input {
+        ClosestBeaconMap::parse(input).unwrap().1
+    }
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        input.count_covered_along_row(2_000_000)
+    }
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        let pos = find_distress_beacon(input, 4_000_000).unwrap();
+        pos.x * 4_000_000 + pos.y
+    }
+}
+
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -405,13 +481,55 @@ mod tests {
         };
 
         let res = beacon_map.get_boundary_lines(&Position { x: 8, y: 7 });
-        println!("{:#?}", res);
+        // 8 + 7 = 15 and 8 - 7 = 1, pushed out by a radius of 9 + 1.
+        assert_eq!(res, [DiagonalLine::U(5), DiagonalLine::U(25), DiagonalLine::V(-9), DiagonalLine::V(11)]);
 
     }
 
     #[test]
     fn get_intersection_points() {
-
+        let mut rng = advent::rng::Rng::new(0x2022);
+        let mut next = |range: isize| rng.range(-range..=range);
+
+        for _ in 0..100 {
+            let (sensor1, beacon1) = (Position { x: next(6), y: next(6) }, Position { x: next(6), y: next(6) });
+            let (sensor2, beacon2) = (Position { x: next(6), y: next(6) }, Position { x: next(6), y: next(6) });
+            if sensor1 == sensor2 {
+                continue;
+            }
+            let beacon_map = ClosestBeaconMap {
+                sensor_to_beacon_map: vec![(sensor1, beacon1), (sensor2, beacon2)].into_iter().collect(),
+                occupied_positions: vec![sensor1, sensor2, beacon1, beacon2].into_iter().collect(),
+            };
+
+            let points = beacon_map.get_intersection_points(&sensor1, &sensor2);
+            let just_out_of_reach = |sensor: &Sensor, beacon: &Beacon, position: &Position| {
+                sensor.distance(position) == sensor.distance(beacon) + 1
+            };
+            let (lines1, lines2) = (beacon_map.get_boundary_lines(&sensor1), beacon_map.get_boundary_lines(&sensor2));
+            let on_shared_line = |position: Position| {
+                lines1.iter().any(|line| lines2.contains(line) && line.contains(position.into()))
+            };
+            // Every position just out of reach of both sensors is one of the crossings,
+            // unless the two boundaries run along the same line there.
+            for x in -30..=30 {
+                for y in -30..=30 {
+                    let position = Position { x, y };
+                    if just_out_of_reach(&sensor1, &beacon1, &position)
+                        && just_out_of_reach(&sensor2, &beacon2, &position)
+                        && !on_shared_line(position)
+                    {
+                        assert!(points.contains(&position), "{:?} missing from {:?}", position, points);
+                    }
+                }
+            }
+            // And every crossing is on a boundary line of each.
+            for point in points {
+                let rotated = Rotated::from(point);
+                assert!(lines1.iter().any(|line| line.contains(rotated)));
+                assert!(lines2.iter().any(|line| line.contains(rotated)));
+            }
+        }
     }
     // #[test]
     // fn line_intersection() {
@@ -423,4 +541,73 @@ mod tests {
     //     // assert_eq!(l1.intersection_point(&l2), None);
     //     // assert_eq!(l1.intersection_point(&l3), Some(Position { x: 8, y: 17}));
     // }
-}
\ No newline at end of file
+
+
+    #[test]
+    fn test_smol_part1() {
+        let s = "Sensor at x=8, y=7: closest beacon is at x=2, y=10";
+        assert_eq!(super::solve_part1(s, 10), 12);
+    }
+
+    #[test]
+    fn test_big_part2() {
+        let s = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+        assert_eq!(super::solve_part2(s, 20), Some(Position { x: 14, y: 11}));
+        assert_eq!(super::solve_part2_sweep(s, 20), Some(Position { x: 14, y: 11}));
+    }
+
+
+    #[test]
+    fn test_big_part1() {
+        let s = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+        assert_eq!(super::solve_part1(s, 10), 26);
+
+        let (_, map) = ClosestBeaconMap::parse(s).unwrap();
+        for row in -10..=30 {
+            let positions = map.get_unique_positions_along_row_where_beacon_definitely_doesnt_exist(row);
+            assert_eq!(map.count_covered_along_row(row), positions.len());
+        }
+    }
+    #[test]
+    fn test_input_part1() {
+        assert_eq!(super::solve_part1(include_str!("input.txt"), 2_000_000), 5_125_700);
+    }
+
+    #[test]
+    fn test_input_part2() {
+        let sol = super::solve_part2(include_str!("input.txt"), 4_000_000).unwrap();
+        assert_eq!(4_000_000 * sol.x + sol.y, 11379394658764);
+    }
+
+    #[test]
+    fn test_input_part2_sweep() {
+        let sol = super::solve_part2_sweep(include_str!("input.txt"), 4_000_000).unwrap();
+        assert_eq!(4_000_000 * sol.x + sol.y, 11379394658764);
+    }
+}
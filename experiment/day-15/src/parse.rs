@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-use nom::{IResult, sequence::tuple, character::{complete::digit1, complete::space0}, combinator::{map_res, map, opt}, bytes::complete::tag, multi::{many0, many1}, branch::alt};
+use nom::{IResult, sequence::tuple, character::complete::digit1, combinator::{map, opt}, bytes::complete::tag, multi::many0, branch::alt};
 
 use crate::{Position, ClosestBeaconMap, Beacon, Sensor};
 
@@ -78,10 +78,9 @@ impl Parse for ClosestBeaconMap {
 }
 
 
-// #[cfg(tests)]
+#[cfg(test)]
 mod tests {
     use super::*;
-    // use test_case::test_case;
 
 
     #[test]
@@ -0,0 +1,212 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::io::Write;
+use std::ops::RangeInclusive;
+
+use crate::{ClosestBeaconMap, Position};
+
+
+/// The part of the map to draw, bounds included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Viewport {
+    pub columns: RangeInclusive<isize>,
+    pub rows: RangeInclusive<isize>,
+}
+
+impl ClosestBeaconMap {
+    /// How far inside some sensor's reach the position is,
+    /// negative if none of them can see it.
+    pub fn coverage_depth(&self, position: &Position) -> isize {
+        self.sensor_to_beacon_map
+            .iter()
+            .map(|(sensor, beacon)| sensor.distance(beacon) - sensor.distance(position))
+            .max()
+            .unwrap_or(-1)
+    }
+
+    pub fn render(&self, viewport: Viewport) -> Render<'_> {
+        Render { map: self, viewport }
+    }
+
+    /// A `size` by `size` picture of the square `0..=upper_bound` on both axes.
+    pub fn heatmap(&self, upper_bound: isize, size: usize) -> Heatmap {
+        let scale = ((upper_bound + 1) as usize).div_ceil(size).max(1) as isize;
+        let depths = (0..size as isize)
+            .flat_map(|row| (0..size as isize).map(move |column| (row, column)))
+            .map(|(row, column)| {
+                let center = Position { x: column * scale + scale / 2, y: row * scale + scale / 2 };
+                self.coverage_depth(&center)
+            })
+            .collect();
+        Heatmap { size, scale, depths, marks: HashSet::new() }
+    }
+}
+
+
+/// A viewport of the map drawn like the puzzle does, with the axes labelled:
+/// `S` for sensors, `B` for beacons, `#` where a sensor can see and `.` everywhere else.
+#[derive(Debug, Clone)]
+pub struct Render<'a> {
+    map: &'a ClosestBeaconMap,
+    viewport: Viewport,
+}
+
+impl Render<'_> {
+    fn cell(&self, position: Position) -> char {
+        if self.map.contains_key(&position) {
+            'S'
+        } else if self.map.values().any(|beacon| *beacon == position) {
+            'B'
+        } else if self.map.coverage_depth(&position) >= 0 {
+            '#'
+        } else {
+            '.'
+        }
+    }
+}
+
+impl Display for Render<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Viewport { columns, rows } = &self.viewport;
+        let margin = rows.clone().map(|row| row.to_string().len()).max().unwrap_or(0);
+
+        // Every fifth column gets its number written downwards above it.
+        let labels = columns
+            .clone()
+            .map(|column| match column % 5 {
+                0 => column.to_string(),
+                _ => String::new(),
+            })
+            .collect::<Vec<_>>();
+        let height = labels.iter().map(String::len).max().unwrap_or(0);
+        for line in 0..height {
+            let digits = labels
+                .iter()
+                .map(|label| match (label.len() + line).checked_sub(height) {
+                    Some(index) => label.as_bytes()[index] as char,
+                    None => ' ',
+                })
+                .collect::<String>();
+            writeln!(f, "{}", format!("{:margin$} {}", "", digits).trim_end())?;
+        }
+
+        for row in rows.clone() {
+            let cells = columns.clone().map(|x| self.cell(Position { x, y: row })).collect::<String>();
+            writeln!(f, "{:>margin$} {}", row, cells)?;
+        }
+        Ok(())
+    }
+}
+
+
+/// How well covered the whole search space is, one pixel per `scale` by `scale` block.
+/// Pixels that might still hide the distress beacon stand out in yellow,
+/// and any marked positions in red.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heatmap {
+    pub size: usize,
+    pub scale: isize,
+    /// The coverage depth at the middle of each block, row by row.
+    pub depths: Vec<isize>,
+    marks: HashSet<usize>,
+}
+
+impl Heatmap {
+    fn pixel_of(&self, position: &Position) -> Option<usize> {
+        let (column, row) = (position.x / self.scale, position.y / self.scale);
+        let in_bounds = |n: isize| (0..self.size as isize).contains(&n);
+        (position.x >= 0 && position.y >= 0 && in_bounds(column) && in_bounds(row))
+            .then_some(row as usize * self.size + column as usize)
+    }
+
+    /// Highlight the block holding `position`, e.g. a part 2 candidate.
+    pub fn mark(&mut self, position: &Position) {
+        if let Some(pixel) = self.pixel_of(position) {
+            self.marks.insert(pixel);
+        }
+    }
+
+    /// Nothing in a block is further than `scale` from its middle, so only
+    /// blocks that aren't that deep inside some sensor's reach can have a gap.
+    pub fn might_have_gap(&self, position: &Position) -> bool {
+        self.pixel_of(position).is_some_and(|pixel| self.depths[pixel] < self.scale)
+    }
+
+    fn colour(&self, pixel: usize, deepest: isize) -> [u8; 3] {
+        match self.depths[pixel] {
+            _ if self.marks.contains(&pixel) => [255, 0, 0],
+            depth if depth < self.scale => [255, 200, 0],
+            depth => {
+                let shade = (40 + 200 * depth / deepest) as u8;
+                [shade, shade, shade]
+            },
+        }
+    }
+
+    /// Write it out as a binary PPM, which most image viewers open as is.
+    pub fn write_ppm<W: Write>(&self, mut out: W) -> std::io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.size, self.size)?;
+        let deepest = self.depths.iter().copied().max().unwrap_or(0).max(1);
+        let bytes = (0..self.depths.len()).flat_map(|pixel| self.colour(pixel, deepest)).collect::<Vec<_>>();
+        out.write_all(&bytes)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parse;
+
+    const EXAMPLE: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+
+    #[test]
+    fn renders_like_the_puzzle() {
+        let (_, map) = ClosestBeaconMap::parse("Sensor at x=8, y=7: closest beacon is at x=2, y=10").unwrap();
+        let render = map.render(Viewport { columns: -2..=25, rows: 9..=11 }).to_string();
+        assert_eq!(render, "               1    1    2    2
+     0    5    0    5    0    5
+ 9 ...###############..........
+10 ....B############...........
+11 .....###########............
+");
+        let render = map.render(Viewport { columns: 6..=10, rows: -2..=-1 }).to_string();
+        assert_eq!(render, "       1
+       0
+-2 ..#..
+-1 .###.
+");
+        assert_eq!(map.render(Viewport { columns: 7..=9, rows: 7..=7 }).to_string(), "7 #S#\n");
+    }
+
+    #[test]
+    fn heatmap_finds_the_distress_beacon() {
+        let (_, map) = ClosestBeaconMap::parse(EXAMPLE).unwrap();
+        let mut heatmap = map.heatmap(20, 7);
+        assert_eq!(heatmap.scale, 3);
+        assert!(heatmap.might_have_gap(&Position { x: 14, y: 11 }));
+        assert!(!heatmap.might_have_gap(&Position { x: 21, y: 0 }));
+
+        heatmap.mark(&Position { x: 14, y: 11 });
+        let mut ppm = vec![];
+        heatmap.write_ppm(&mut ppm).unwrap();
+        let header = b"P6\n7 7\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 7 * 7 * 3);
+        let pixel = header.len() + (3 * 7 + 4) * 3;
+        assert_eq!(&ppm[pixel..pixel + 3], &[255, 0, 0]);
+    }
+}
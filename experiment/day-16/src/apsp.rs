@@ -0,0 +1,180 @@
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use crate::{Graph, Node};
+
+
+/// Shortest distances between every pair of nodes `0..len()`,
+/// kept row by row in one flat `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct APSP {
+    len: usize,
+    distances: Vec<isize>,
+}
+
+impl APSP {
+    /// The distance between nodes that can't reach each other.
+    pub const UNREACHABLE: isize = isize::MAX;
+
+    /// Nothing can reach anything but itself yet.
+    pub fn new(len: usize) -> Self {
+        let mut apsp = Self { len, distances: vec![Self::UNREACHABLE; len * len] };
+        for node in 0..len {
+            apsp[(node, node)] = 0;
+        }
+        apsp
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn row(&self, from: usize) -> &[isize] {
+        &self.distances[from * self.len..(from + 1) * self.len]
+    }
+
+    /// Only the given nodes, renumbered in the order given.
+    pub fn restrict(&self, nodes: &[usize]) -> APSP {
+        let mut restricted = APSP::new(nodes.len());
+        for (i, &from) in nodes.iter().enumerate() {
+            for (j, &to) in nodes.iter().enumerate() {
+                restricted[(i, j)] = self[(from, to)];
+            }
+        }
+        restricted
+    }
+}
+
+impl Index<(usize, usize)> for APSP {
+    type Output = isize;
+
+    fn index(&self, (from, to): (usize, usize)) -> &Self::Output {
+        &self.distances[from * self.len + to]
+    }
+}
+
+impl IndexMut<(usize, usize)> for APSP {
+    fn index_mut(&mut self, (from, to): (usize, usize)) -> &mut Self::Output {
+        &mut self.distances[from * self.len + to]
+    }
+}
+
+impl Index<(Node, Node)> for APSP {
+    type Output = isize;
+
+    fn index(&self, (from, to): (Node, Node)) -> &Self::Output {
+        &self[(from as usize, to as usize)]
+    }
+}
+
+impl Display for APSP {
+    /// A table of distances, `-` where there's no path.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let header = (0..self.len).map(|n| n.to_string()).collect::<Vec<_>>().join("\t");
+        writeln!(f, "    \t{}", header)?;
+
+        for from in 0..self.len {
+            write!(f, "{} ", from)?;
+            for &distance in self.row(from) {
+                match distance {
+                    Self::UNREACHABLE => write!(f, "\t-")?,
+                    distance => write!(f, "\t{}", distance)?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+
+impl Graph {
+    /// Straightforward Floyd-Warshall implementation for all pairs shortest path algorithm.
+    pub fn all_pairs_shortest_paths(&self) -> APSP {
+        let mut distances = APSP::new(self.names.len());
+
+        for (from, to) in self.to.iter().flat_map(|(from, to)| to.iter().map(move |to| (*from, *to))) {
+            if from != to {
+                distances[(from as usize, to as usize)] = 1;
+            }
+        }
+
+        let len = distances.len();
+        for k in 0..len {
+            for i in 0..len {
+                let dist_ik = distances[(i, k)];
+                if dist_ik == APSP::UNREACHABLE {
+                    continue;
+                }
+                for j in 0..len {
+                    let dist_kj = distances[(k, j)];
+                    if dist_kj == APSP::UNREACHABLE {
+                        continue;
+                    }
+                    if dist_ik + dist_kj < distances[(i, j)] {
+                        distances[(i, j)] = dist_ik + dist_kj;
+                    }
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Every tunnel takes a minute, so a breadth first search
+    /// from each node gets the same distances in O(V * E).
+    pub fn shortest_paths_bfs(&self) -> APSP {
+        let mut distances = APSP::new(self.names.len());
+
+        for source in 0..distances.len() {
+            let mut queue = VecDeque::from([source as Node]);
+            while let Some(node) = queue.pop_front() {
+                let distance = distances[(source, node as usize)];
+                for &next in self.to.get(&node).into_iter().flatten() {
+                    if distances[(source, next as usize)] == APSP::UNREACHABLE {
+                        distances[(source, next as usize)] = distance + 1;
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        distances
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_input_graph;
+
+    #[test]
+    fn bfs_agrees_with_floyd_warshall() {
+        let graph = get_input_graph(include_str!("input.txt"));
+        assert_eq!(graph.shortest_paths_bfs(), graph.all_pairs_shortest_paths());
+    }
+
+    #[test]
+    fn renders_a_table() {
+        let graph = get_input_graph("Valve AA has flow rate=0; tunnel leads to valve BB
+Valve BB has flow rate=1; tunnels lead to valves AA, CC
+Valve CC has flow rate=2; tunnel leads to valve CC");
+        let distances = graph.shortest_paths_bfs();
+        assert_eq!(distances, graph.all_pairs_shortest_paths());
+        assert_eq!(distances.to_string(), "    \t0\t1\t2
+0 \t0\t1\t2
+1 \t1\t0\t1
+2 \t-\t-\t0
+");
+        assert_eq!(distances.restrict(&[2, 0]).to_string(), "    \t0\t1
+0 \t0\t-
+1 \t2\t0
+");
+    }
+}
@@ -0,0 +1,104 @@
+use std::{fmt::Debug, hash::Hash};
+
+
+/// A set of valves, by their index among the valves worth opening.
+/// Small caves fit in a single integer; bigger ones fall back to [`DynamicSet`].
+pub trait ValveSet: Clone + Debug + Eq + Hash {
+    /// Room for valves `0..capacity`.
+    fn empty(capacity: usize) -> Self;
+    fn contains(&self, valve: usize) -> bool;
+    fn insert(&mut self, valve: usize);
+    fn is_disjoint(&self, other: &Self) -> bool;
+    fn union(&self, other: &Self) -> Self;
+
+    fn with(&self, valve: usize) -> Self {
+        let mut set = self.clone();
+        set.insert(valve);
+        set
+    }
+}
+
+macro_rules! integer_valve_set {
+    ($($int:ty),*) => {
+        $(
+            impl ValveSet for $int {
+                fn empty(capacity: usize) -> Self {
+                    assert!(capacity <= <$int>::BITS as usize, "{} valves don't fit in a {}.", capacity, stringify!($int));
+                    0
+                }
+                fn contains(&self, valve: usize) -> bool {
+                    self & (1 << valve) != 0
+                }
+                fn insert(&mut self, valve: usize) {
+                    *self |= 1 << valve;
+                }
+                fn is_disjoint(&self, other: &Self) -> bool {
+                    self & other == 0
+                }
+                fn union(&self, other: &Self) -> Self {
+                    self | other
+                }
+            }
+        )*
+    };
+}
+
+integer_valve_set!(u64, u128);
+
+
+/// As many 64 bit words as it takes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DynamicSet(Vec<u64>);
+
+impl ValveSet for DynamicSet {
+    fn empty(capacity: usize) -> Self {
+        DynamicSet(vec![0; capacity.div_ceil(64)])
+    }
+    fn contains(&self, valve: usize) -> bool {
+        self.0[valve / 64] & (1 << (valve % 64)) != 0
+    }
+    fn insert(&mut self, valve: usize) {
+        self.0[valve / 64] |= 1 << (valve % 64);
+    }
+    fn is_disjoint(&self, other: &Self) -> bool {
+        self.0.iter().zip(&other.0).all(|(a, b)| a & b == 0)
+    }
+    fn union(&self, other: &Self) -> Self {
+        DynamicSet(self.0.iter().zip(&other.0).map(|(a, b)| a | b).collect())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<S: ValveSet>(capacity: usize) {
+        let empty = S::empty(capacity);
+        let set = empty.with(0).with(capacity - 1).with(capacity / 2);
+        for valve in 0..capacity {
+            assert!(!empty.contains(valve));
+            assert_eq!(set.contains(valve), [0, capacity / 2, capacity - 1].contains(&valve));
+        }
+        assert_eq!(set.with(0), set);
+
+        let other = empty.with(1).with(capacity - 2);
+        assert!(set.is_disjoint(&other) && !set.is_disjoint(&set.with(1)));
+        let union = set.union(&other);
+        assert!((0..capacity).all(|valve| union.contains(valve) == (set.contains(valve) || other.contains(valve))));
+    }
+
+    #[test]
+    fn sets_hold_what_fits() {
+        round_trip::<u64>(64);
+        round_trip::<u128>(128);
+        round_trip::<DynamicSet>(64);
+        round_trip::<DynamicSet>(300);
+    }
+
+    #[test]
+    #[should_panic]
+    fn u64_is_not_enough_for_65() {
+        u64::empty(65);
+    }
+}
@@ -1,9 +1,21 @@
 #![allow(unused)]
 
+mod apsp;
+mod bitset;
 mod parse;
+mod planner;
+#[cfg(test)]
+mod test_support;
+mod timeline;
+mod valves;
 
+pub use apsp::*;
+pub use bitset::*;
 pub use parse::*;
-use rayon::prelude::*;
+pub use planner::*;
+pub use timeline::*;
+pub use valves::*;
+use advent::Solution;
 
 use std::{
     collections::{HashMap, HashSet},
@@ -12,163 +24,81 @@ use std::{
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Graph {
-    pub(crate) to: HashMap<u8, Vec<u8>>,
-    pub flow_rates: HashMap<u8, isize>,
-    pub non_zero_flow_indices: Vec<u8>,
+    pub(crate) to: HashMap<Node, Vec<Node>>,
+    pub flow_rates: HashMap<Node, isize>,
+    pub non_zero_flow_indices: Vec<Node>,
+    /// The valves' names, indexed by node.
+    pub names: Vec<String>,
 }
 
-pub type APSP = HashMap<u8, HashMap<u8, isize>>;
-
-// There's less than 63 nodes, so we can use a i64 to represent a set of indices
-// where the only bits set are the indices of those nodes.
-pub type State = i64;
+/// Index of a valve in the sorted list of names.
+pub type Node = u16;
 
 impl Graph {
-    pub fn nodes(&self) -> impl Iterator<Item = u8> + '_ {
+    pub fn nodes(&self) -> impl Iterator<Item = Node> + '_ {
         self.to.keys().cloned()
     }
-    pub fn mask(&self, node: &u8) -> i64 {
-        1 << *node
-    }
-    pub fn edges(&self) -> impl Iterator<Item = (&u8, &u8, isize)> + '_ {
+    pub fn edges(&self) -> impl Iterator<Item = (&Node, &Node, isize)> + '_ {
         self.to.iter().flat_map(move |(from, to)| {
             to.iter()
                 .map(move |to| (from, to, *self.flow_rates.get(from).unwrap()))
         })
     }
-    pub fn flow_of(&self, node: &u8) -> Option<isize> {
+    pub fn flow_of(&self, node: &Node) -> Option<isize> {
         self.flow_rates.get(node).cloned()
     }
-
-    /// Straightforward Floyd-Warshall implementation for all pairs shortest path algorithm.
-    pub fn all_pairs_shortest_paths(&self) -> APSP {
-        let mut distances = HashMap::new();
-
-        for node1 in self.to.keys() {
-            for node2 in self.to.keys() {
-                distances.insert((node1, node2), isize::MAX);
-            }
-        }
-
-        for (from, to, _) in self.edges() {
-            distances.insert((from, to), 1isize);
-        }
-
-        for node in self.to.keys() {
-            distances.insert((node, node), 0isize);
-        }
-
-        for k in self.to.keys() {
-            for i in self.to.keys() {
-                for j in self.to.keys() {
-                    let dist_ik = distances.get(&(i, k)).unwrap();
-                    let dist_kj = distances.get(&(k, j)).unwrap();
-                    if dist_ik == &isize::MAX || dist_kj == &isize::MAX {
-                        continue;
-                    }
-                    let dist = dist_ik + dist_kj;
-                    if dist < *distances.get(&(i, j)).unwrap() {
-                        distances.insert((i, j), dist);
-                    }
-                }
-            }
-        }
-
-        let mut results = HashMap::new();
-        for ((start, end), distance) in distances.into_iter() {
-            results
-                .entry(*start)
-                .or_insert_with(HashMap::new)
-                .insert(*end, distance);
-        }
-
-        results
+    pub fn name(&self, node: Node) -> &str {
+        &self.names[node as usize]
+    }
+    pub fn index_of(&self, name: &str) -> Option<Node> {
+        self.names.binary_search_by(|n| n.as_str().cmp(name)).ok().map(|idx| idx as Node)
     }
 
     pub fn visualize_all_pair_shortest_paths(&self) {
-        let shortest_paths = self.all_pairs_shortest_paths();
-
-        let mut nodes = self.to.keys().copied().collect::<Vec<_>>();
-        nodes.sort();
-        println!(
-            "    \t{}",
-            nodes
-                .iter()
-                .map(|n| format!("{}", n))
-                .collect::<Vec<String>>()
-                .join("\t")
-        );
-
-        for node1 in nodes.iter() {
-            print!("{} ", node1);
-            let mut distances = shortest_paths
-                .get(&(node1.clone()))
-                .unwrap()
-                .iter()
-                .collect::<Vec<_>>();
-            distances.sort_by_key(|&d| d.0);
-
-            for (_, dist) in distances.iter() {
-                print!("\t{}", dist);
-            }
-            println!();
-        }
+        print!("{}", self.all_pairs_shortest_paths());
     }
 
-    /// Thanks to [JuniorBirdman1115's Reddit post],
-    /// the key insight is to store the visited state
-    /// as a bitset (and since there are less than 63 nodes)
-    /// we can use a i64 to store it.
-    ///
-    /// [JuniorBirdman1115's Reddit post]: https://www.reddit.com/r/adventofcode/comments/zn6k1l/comment/j0oo5a9/
-    pub fn visit<'a>(
-        &self,
-        current_node: u8,
-        budget: i64,
-        state: State,
-        distances: &APSP,
-        flow: i64,
-        answer: &'a mut HashMap<State, i64>,
-    ) {
-        // Update our cache if a better flow is achieved.
-        answer
-            .entry(state)
-            .and_modify(|e| *e = flow.max(*e))
-            .or_insert_with(|| flow.max(0));
-
-        // For each node that has a non-zero flow,
-        // see if we can visit that.
-        self.non_zero_flow_indices.iter().for_each(|next_node| {
-            let distance = *distances
-                .get(&current_node)
-                .unwrap()
-                .get(next_node)
-                .unwrap() as i64;
-
-            // If we choose to go here, then we have to travel the shortest distance to get there
-            // and then turn the valve on, using up `(distance + 1)` time.
-            let new_budget = budget - (distance + 1);
-
-            let mask = self.mask(next_node);
-            let unvisited = (state & mask) == 0;
-
-            // If not already visited and have budget to move, take that path and record the best flow
-            // along it.
-            if unvisited && new_budget >= 0 {
-                let flow_from_neighbor = self.flow_of(next_node).unwrap() as i64;
-                self.visit(
-                    *next_node,
-                    new_budget,
-                    state | mask,
-                    distances,
-                    flow + (new_budget * flow_from_neighbor),
-                    answer,
-                );
-            }
-        });
+}
+
+
+pub fn get_input_graph(s: &str) -> Graph {
+    let (_, graph) = parse_graph(s).unwrap();
+    graph
+}
+
+pub fn solve_part1(graph: &Graph, distances: &APSP) -> i64 {
+    let start = graph.index_of("AA").unwrap();
+    graph.compress(distances, start).max_release(30)
+}
+
+/// Us and the elephant, splitting up the valves between us.
+pub fn solve_part2(graph: &Graph, distances: &APSP) -> i64 {
+    let start = graph.index_of("AA").unwrap();
+    Planner::new(2, 26, start).plan(graph, distances).total
+}
+
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = (Graph, APSP);
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &str) -> Self::Input {
+        let graph = get_input_graph(input);
+        let distances = graph.shortest_paths_bfs();
+        (graph, distances)
+    }
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        solve_part1(&input.0, &input.1)
+    }
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        solve_part2(&input.0, &input.1)
     }
 }
 
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -191,4 +121,35 @@ Valve JJ has flow rate=21; tunnel leads to valve II";
         assert_eq!(rem, "");
         assert_eq!(graph.to.len(), 10);
     }
+
+
+    fn get_smol_input() -> Graph {
+        let s = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II";
+        get_input_graph(s)
+    }
+
+    #[test]
+    fn smol() {
+        let graph = get_smol_input();
+        let distances = graph.all_pairs_shortest_paths();
+        assert_eq!(1651, solve_part1(&graph, &distances));
+        assert_eq!(1707, solve_part2(&graph, &distances));
+    }
+
+    #[test]
+    fn big() {
+        let graph = get_input_graph(include_str!("input.txt"));
+        let distances = graph.all_pairs_shortest_paths();
+        assert_eq!(solve_part1(&graph, &distances), 1940);
+        assert_eq!(solve_part2(&graph, &distances), 2469);
+    }
 }
@@ -33,22 +33,22 @@ where
         let mut indices = starting_nodes
             .iter()
             .enumerate()
-            .map(|(idx, _)| idx as u8)
+            .map(|(idx, _)| idx as Node)
             .collect::<Vec<_>>();
         let mut non_zero_indices = Vec::new();
 
         for (idx, tunnels) in all_tunnels.iter().enumerate() {
             let from = tunnels.from.clone();
-            let from_index = starting_nodes.binary_search(&from).unwrap() as u8;
+            let from_index = starting_nodes.binary_search(&from).unwrap() as Node;
 
             let neighbors = tunnels
                 .to
                 .iter()
-                .map(|t| starting_nodes.binary_search(t).unwrap() as u8)
+                .map(|t| starting_nodes.binary_search(t).unwrap() as Node)
                 .collect::<Vec<_>>();
 
             hmap.insert(from_index, neighbors);
-            // hmap.insert(idx as u8, tunnels.to.iter().map(|t| all_tunnels.binary_search_by_key(&t, |z| z.from.clone())).collect::<Vec<_>>());
+            // hmap.insert(idx as Node, tunnels.to.iter().map(|t| all_tunnels.binary_search_by_key(&t, |z| z.from.clone())).collect::<Vec<_>>());
             flow_map.insert(from_index, tunnels.flow_rate);
             if tunnels.flow_rate > 0 {
                 non_zero_indices.push(from_index);
@@ -59,6 +59,7 @@ where
             to: hmap,
             flow_rates: flow_map,
             non_zero_flow_indices: non_zero_indices,
+            names: starting_nodes,
         }
     }
 }
@@ -0,0 +1,325 @@
+use crate::{DynamicSet, Graph, Node, ValveSet, Valves, APSP};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+
+/// A valve being opened during the given minute (counting from 1),
+/// after which it releases `flow` pressure every minute until time runs out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Opening {
+    pub valve: Node,
+    pub minute: i64,
+    pub flow: i64,
+}
+
+/// The valves one agent opens, in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Schedule {
+    pub openings: Vec<Opening>,
+}
+
+impl Schedule {
+    /// Total pressure released by this agent's valves within `budget` minutes.
+    pub fn released(&self, budget: i64) -> i64 {
+        self.openings
+            .iter()
+            .map(|opening| opening.flow * (budget - opening.minute))
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub total: i64,
+    /// One for each agent.
+    pub schedules: Vec<Schedule>,
+    pub budget: i64,
+    pub start: Node,
+}
+
+
+/// The most valves [`Planner`] keeps a table over every subset of.
+/// Adding an agent to a team costs `3^n` there, which is still quick at 16.
+pub const DENSE_VALVES: usize = 16;
+
+
+/// Any number of agents setting off from the same valve at the same time,
+/// each opening a different set of valves.
+///
+/// Since no valve is worth opening twice, the agents never need to coordinate
+/// beyond splitting up the valves. So a single agent's search gives the best
+/// release for every set of valves it can reach, and the agents' plans are
+/// combined over disjoint sets of valves afterwards.
+///
+/// With at most [`DENSE_VALVES`] valves, that's a subset-max pass over a table of
+/// every set of valves. Past that the table gets too big, so the sets the search
+/// actually reached get paired up one agent at a time instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Planner {
+    pub agents: usize,
+    /// Minutes everyone has.
+    pub budget: i64,
+    pub start: Node,
+}
+
+impl Planner {
+    pub fn new(agents: usize, budget: i64, start: Node) -> Self {
+        Self { agents, budget, start }
+    }
+
+    /// The best release and route one agent can manage for each set of valves it can open in time,
+    /// most pressure first.
+    fn explore<S: ValveSet>(&self, valves: &Valves) -> Vec<(S, i64, Vec<Opening>)> {
+        let mut answer = HashMap::new();
+        let mut routes = HashMap::new();
+        let state = S::empty(valves.len());
+        valves.visit(valves.start_index(), self.budget, state, 0, &mut vec![], &mut answer, Some(&mut routes));
+
+        let mut best = answer
+            .into_iter()
+            .map(|(opened, flow)| {
+                let openings = routes.remove(&opened).unwrap().openings;
+                (opened, flow, openings)
+            })
+            .collect::<Vec<_>>();
+        best.sort_by_key(|(_, flow, _)| Reverse(*flow));
+        best
+    }
+
+    pub fn plan(&self, graph: &Graph, distances: &APSP) -> Plan {
+        if self.agents == 0 {
+            return Plan { total: 0, schedules: vec![], budget: self.budget, start: self.start };
+        }
+
+        let valves = graph.compress(distances, self.start);
+        let (total, schedules) = match valves.len() {
+            n if n <= DENSE_VALVES => self.combine_dense(n, &self.explore::<u64>(&valves)),
+            0..=64 => self.combine_reached(&self.explore::<u64>(&valves)),
+            65..=128 => self.combine_reached(&self.explore::<u128>(&valves)),
+            _ => self.combine_reached(&self.explore::<DynamicSet>(&valves)),
+        };
+        Plan { total, schedules, budget: self.budget, start: self.start }
+    }
+
+    /// Split `n` valves between the agents with a table over every subset of them.
+    fn combine_dense(&self, n: usize, best: &[(u64, i64, Vec<Opening>)]) -> (i64, Vec<Schedule>) {
+        let full = (1 << n) - 1;
+        let mut exact = vec![i64::MIN; full + 1];
+        let mut routes = vec![None; full + 1];
+        for (opened, flow, openings) in best {
+            exact[*opened as usize] = *flow;
+            routes[*opened as usize] = Some(openings);
+        }
+
+        // The best one agent can do opening some subset of each set of valves,
+        // and which subset that is. Pushed up from subsets to supersets one valve at a time.
+        let mut subset_max = exact.iter().copied().zip(0..).collect::<Vec<_>>();
+        for bit in 0..n {
+            for mask in 0..=full {
+                if mask & (1 << bit) != 0 {
+                    let without = subset_max[mask ^ (1 << bit)];
+                    if without.0 > subset_max[mask].0 {
+                        subset_max[mask] = without;
+                    }
+                }
+            }
+        }
+
+        // `teams[j][mask]` is the best `j + 1` agents can do between them using only the
+        // valves in `mask`, along with the valves the last of them opens.
+        // Only the last team needs the full set of valves.
+        let mut teams = vec![subset_max];
+        for j in 1..self.agents {
+            let previous = &teams[j - 1];
+            let masks = if j + 1 == self.agents { full..=full } else { 0..=full };
+
+            let mut team = vec![(i64::MIN, 0); full + 1];
+            for mask in masks {
+                // Every subset of `mask`, for the newest agent to open.
+                let mut own = mask;
+                loop {
+                    if exact[own] != i64::MIN && previous[mask ^ own].0 != i64::MIN {
+                        let total = exact[own] + previous[mask ^ own].0;
+                        if total > team[mask].0 {
+                            team[mask] = (total, own);
+                        }
+                    }
+                    if own == 0 {
+                        break;
+                    }
+                    own = (own - 1) & mask;
+                }
+            }
+            teams.push(team);
+        }
+
+        let total = teams[self.agents - 1][full].0;
+        let mut schedules = vec![];
+        let mut remaining = full;
+        for team in teams.iter().rev() {
+            let own = team[remaining].1;
+            let openings = routes[own].cloned().unwrap_or_default();
+            schedules.push(Schedule { openings });
+            remaining ^= own;
+        }
+        schedules.reverse();
+        (total, schedules)
+    }
+
+    /// Split the valves between the agents, only ever pairing up
+    /// sets of valves the search actually reached.
+    fn combine_reached<S: ValveSet>(&self, best: &[(S, i64, Vec<Opening>)]) -> (i64, Vec<Schedule>) {
+        // The best a team can do for every set of valves its members open between them,
+        // and who opens what, as indices into `best`. Most pressure first.
+        let mut teams = best
+            .iter()
+            .enumerate()
+            .map(|(own, (opened, flow, _))| (opened.clone(), *flow, vec![own]))
+            .collect::<Vec<_>>();
+
+        for _ in 2..self.agents {
+            let mut bigger: HashMap<S, (i64, Vec<usize>)> = HashMap::new();
+            for (opened, total, members) in &teams {
+                for (own, (theirs, flow, _)) in best.iter().enumerate() {
+                    if !opened.is_disjoint(theirs) {
+                        continue;
+                    }
+                    let together = opened.union(theirs);
+                    if bigger.get(&together).is_none_or(|(best_total, _)| total + flow > *best_total) {
+                        let members = members.iter().copied().chain([own]).collect();
+                        bigger.insert(together, (total + flow, members));
+                    }
+                }
+            }
+            teams = bigger.into_iter().map(|(opened, (total, members))| (opened, total, members)).collect();
+            teams.sort_by_key(|(_, total, _)| Reverse(*total));
+        }
+
+        // The last agent only has to beat the best total so far, and both lists
+        // are sorted, so whole stretches of them can be skipped.
+        let (mut total, mut members) = (teams[0].1, teams[0].2.clone());
+        if self.agents > 1 {
+            total = i64::MIN;
+            for (opened, team_total, team) in &teams {
+                if team_total + best[0].1 <= total {
+                    break;
+                }
+                for (own, (theirs, flow, _)) in best.iter().enumerate() {
+                    if team_total + flow <= total {
+                        break;
+                    }
+                    if opened.is_disjoint(theirs) {
+                        total = team_total + flow;
+                        members = team.iter().copied().chain([own]).collect();
+                    }
+                }
+            }
+        }
+
+        let schedules = members
+            .into_iter()
+            .map(|own| Schedule { openings: best[own].2.clone() })
+            .collect();
+        (total, schedules)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_input_graph;
+    use crate::test_support::corridor;
+
+    const EXAMPLE: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II";
+
+    #[test]
+    fn plans_for_one_agent() {
+        let graph = get_input_graph(EXAMPLE);
+        let distances = graph.all_pairs_shortest_paths();
+        let plan = Planner::new(1, 30, 0).plan(&graph, &distances);
+        assert_eq!(plan.total, 1651);
+
+        // DD, BB, JJ, HH, EE, CC as in the puzzle's walkthrough.
+        let schedule = &plan.schedules[0];
+        let valves = schedule.openings.iter().map(|opening| opening.valve).collect::<Vec<_>>();
+        assert_eq!(valves, vec![3, 1, 9, 7, 4, 2]);
+        let minutes = schedule.openings.iter().map(|opening| opening.minute).collect::<Vec<_>>();
+        assert_eq!(minutes, vec![2, 5, 9, 17, 21, 24]);
+        assert_eq!(schedule.released(30), 1651);
+    }
+
+    #[test]
+    fn plans_for_a_team() {
+        let graph = get_input_graph(EXAMPLE);
+        let distances = graph.all_pairs_shortest_paths();
+
+        let plan = Planner::new(2, 26, 0).plan(&graph, &distances);
+        assert_eq!(plan.total, 1707);
+        assert_eq!(plan.schedules.len(), 2);
+        assert_eq!(plan.schedules.iter().map(|schedule| schedule.released(26)).sum::<i64>(), 1707);
+
+        // Each agent opens different valves.
+        let mut valves = plan
+            .schedules
+            .iter()
+            .flat_map(|schedule| schedule.openings.iter().map(|opening| opening.valve))
+            .collect::<Vec<_>>();
+        let opened = valves.len();
+        valves.sort();
+        valves.dedup();
+        assert_eq!(valves.len(), opened);
+
+        // With one agent per valve, each valve gets opened as soon as someone can walk to it.
+        let crowd = Planner::new(6, 26, 0).plan(&graph, &distances);
+        assert_eq!(crowd.schedules.len(), 6);
+        assert!(crowd.schedules.iter().all(|schedule| schedule.openings.len() == 1));
+        assert_eq!(crowd.total, 13 * 24 + 2 * 23 + 20 * 24 + 3 * 23 + 22 * 20 + 21 * 23);
+        let nobody = Planner::new(0, 26, 0).plan(&graph, &distances);
+        assert_eq!((nobody.total, nobody.schedules.len()), (0, 0));
+    }
+
+    #[test]
+    fn both_ways_of_combining_agree() {
+        for (s, budget) in [(EXAMPLE.to_string(), 26), (corridor(12), 12)] {
+            let graph = get_input_graph(&s);
+            let distances = graph.all_pairs_shortest_paths();
+            let valves = graph.compress(&distances, 0);
+            let best = Planner::new(1, budget, 0).explore::<u64>(&valves);
+
+            for agents in 1..=3 {
+                let planner = Planner::new(agents, budget, 0);
+                let (dense, dense_schedules) = planner.combine_dense(valves.len(), &best);
+                let (reached, reached_schedules) = planner.combine_reached(&best);
+                assert_eq!(dense, reached, "{} agents", agents);
+
+                let released = |schedules: &[Schedule]| schedules.iter().map(|schedule| schedule.released(budget)).sum::<i64>();
+                assert_eq!(released(&dense_schedules), dense);
+                assert_eq!(released(&reached_schedules), reached);
+            }
+        }
+    }
+
+    #[test]
+    fn plans_for_a_team_in_big_caves() {
+        // One agent takes the first valves, one every two minutes: 6 + 4 + 2.
+        // The other one walks past them and picks up 3 + 1 further along.
+        for len in [30, 100, 300] {
+            let graph = get_input_graph(&corridor(len));
+            let distances = graph.shortest_paths_bfs();
+            let start = graph.index_of("AAA").unwrap();
+            let plan = Planner::new(2, 8, start).plan(&graph, &distances);
+            assert_eq!(plan.total, 16, "{} valves", len - 1);
+            assert_eq!(plan.schedules.iter().map(|schedule| schedule.released(8)).sum::<i64>(), 16);
+        }
+    }
+}
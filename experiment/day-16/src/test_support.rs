@@ -0,0 +1,19 @@
+//! Fixtures shared by the tests of more than one module.
+
+/// A long corridor of valves that all release 1, starting at one end.
+pub fn corridor(len: usize) -> String {
+    let letter = |n: usize| (b'A' + n as u8) as char;
+    let name = |idx: usize| format!("{}{}{}", letter(idx / 676), letter(idx / 26 % 26), letter(idx % 26));
+    (0..len)
+        .map(|idx| {
+            let flow = if idx == 0 { 0 } else { 1 };
+            let neighbors = [idx.checked_sub(1), Some(idx + 1).filter(|&next| next < len)]
+                .into_iter()
+                .flatten()
+                .map(name)
+                .collect::<Vec<_>>();
+            format!("Valve {} has flow rate={}; tunnels lead to valves {}", name(idx), flow, neighbors.join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,177 @@
+use std::fmt::Display;
+
+use crate::{Graph, Node, Plan, Schedule, APSP};
+
+
+/// What an agent is busy with during a minute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Move(Node),
+    Open(Node),
+    Idle,
+}
+
+impl Schedule {
+    /// Fill in the walks between the valves, one step per minute,
+    /// going the same way the shortest paths do.
+    pub fn actions(&self, graph: &Graph, distances: &APSP, start: Node, budget: i64) -> Vec<Action> {
+        let mut actions = vec![];
+        let mut position = start;
+
+        for opening in &self.openings {
+            while position != opening.valve {
+                let remaining = distances[(position, opening.valve)];
+                position = *graph.to[&position]
+                    .iter()
+                    .find(|&&next| distances[(next, opening.valve)] == remaining - 1)
+                    .expect("Every step along a shortest path gets one closer.");
+                actions.push(Action::Move(position));
+            }
+            actions.push(Action::Open(opening.valve));
+            debug_assert_eq!(actions.len() as i64, opening.minute);
+        }
+
+        actions.resize(budget.max(0) as usize, Action::Idle);
+        actions
+    }
+}
+
+impl Plan {
+    pub fn timeline<'a>(&'a self, graph: &'a Graph, distances: &APSP) -> Timeline<'a> {
+        let actions = self
+            .schedules
+            .iter()
+            .map(|schedule| schedule.actions(graph, distances, self.start, self.budget))
+            .collect();
+        Timeline { graph, plan: self, actions }
+    }
+}
+
+
+/// A plan narrated minute by minute, the way the puzzle does it.
+#[derive(Debug, Clone)]
+pub struct Timeline<'a> {
+    graph: &'a Graph,
+    plan: &'a Plan,
+    /// For each agent, what they do every minute.
+    actions: Vec<Vec<Action>>,
+}
+
+impl Timeline<'_> {
+    /// How the narration refers to an agent, and how it moves and opens valves.
+    fn agent(index: usize) -> (String, &'static str, &'static str) {
+        match index {
+            0 => ("You".to_string(), "move", "open"),
+            1 => ("The elephant".to_string(), "moves", "opens"),
+            _ => (format!("Agent {}", index + 1), "moves", "opens"),
+        }
+    }
+
+    /// "Valves BB, DD, and JJ are open, releasing 54 pressure."
+    fn open_valves(&self, minute: i64) -> String {
+        let mut open = self
+            .plan
+            .schedules
+            .iter()
+            .flat_map(|schedule| schedule.openings.iter())
+            .filter(|opening| opening.minute < minute)
+            .collect::<Vec<_>>();
+        open.sort_by_key(|opening| self.graph.name(opening.valve));
+
+        let names = open.iter().map(|opening| self.graph.name(opening.valve)).collect::<Vec<_>>();
+        let released = open.iter().map(|opening| opening.flow).sum::<i64>();
+
+        match names.as_slice() {
+            [] => "No valves are open.".to_string(),
+            [name] => format!("Valve {} is open, releasing {} pressure.", name, released),
+            [first, second] => {
+                format!("Valves {} and {} are open, releasing {} pressure.", first, second, released)
+            },
+            [rest @ .., last] => format!(
+                "Valves {}, and {} are open, releasing {} pressure.",
+                rest.join(", "),
+                last,
+                released
+            ),
+        }
+    }
+}
+
+impl Display for Timeline<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for minute in 1..=self.plan.budget {
+            if minute > 1 {
+                writeln!(f)?;
+            }
+            writeln!(f, "== Minute {} ==", minute)?;
+            writeln!(f, "{}", self.open_valves(minute))?;
+
+            for (index, actions) in self.actions.iter().enumerate() {
+                let (who, moves, opens) = Self::agent(index);
+                match actions[minute as usize - 1] {
+                    Action::Move(to) => writeln!(f, "{} {} to valve {}.", who, moves, self.graph.name(to))?,
+                    Action::Open(valve) => writeln!(f, "{} {} valve {}.", who, opens, self.graph.name(valve))?,
+                    Action::Idle => {},
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{get_input_graph, Planner};
+
+    const EXAMPLE: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II";
+
+    #[test]
+    fn narrates_like_the_puzzle() {
+        let graph = get_input_graph(EXAMPLE);
+        let distances = graph.all_pairs_shortest_paths();
+        let start = graph.index_of("AA").unwrap();
+        let plan = Planner::new(1, 30, start).plan(&graph, &distances);
+
+        let timeline = plan.timeline(&graph, &distances).to_string();
+        let minutes = timeline.split("\n\n").collect::<Vec<_>>();
+        assert_eq!(minutes.len(), 30);
+        assert_eq!(minutes[0], "== Minute 1 ==\nNo valves are open.\nYou move to valve DD.");
+        assert_eq!(minutes[1], "== Minute 2 ==\nNo valves are open.\nYou open valve DD.");
+        assert_eq!(minutes[2], "== Minute 3 ==\nValve DD is open, releasing 20 pressure.\nYou move to valve CC.");
+        assert_eq!(
+            minutes[4],
+            "== Minute 5 ==\nValve DD is open, releasing 20 pressure.\nYou open valve BB."
+        );
+        assert_eq!(
+            minutes[9],
+            "== Minute 10 ==\nValves BB, DD, and JJ are open, releasing 54 pressure.\nYou move to valve II."
+        );
+        assert_eq!(
+            minutes[29],
+            "== Minute 30 ==\nValves BB, CC, DD, EE, HH, and JJ are open, releasing 81 pressure.\n"
+        );
+    }
+
+    #[test]
+    fn narrates_the_elephant_too() {
+        let graph = get_input_graph(EXAMPLE);
+        let distances = graph.all_pairs_shortest_paths();
+        let plan = Planner::new(2, 26, graph.index_of("AA").unwrap()).plan(&graph, &distances);
+
+        let timeline = plan.timeline(&graph, &distances).to_string();
+        let minutes = timeline.split("\n\n").collect::<Vec<_>>();
+        assert_eq!(minutes.len(), 26);
+        assert!(timeline.contains("The elephant opens valve"));
+        assert!(minutes[25].starts_with("== Minute 26 ==\nValves BB, CC, DD, EE, HH, and JJ are open, releasing 81 pressure."));
+    }
+}
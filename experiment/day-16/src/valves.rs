@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use crate::{DynamicSet, Graph, Node, Opening, Schedule, ValveSet, APSP};
+
+
+/// The cave boiled down to what the search cares about: the valves with any flow,
+/// where we start, and how long it takes to walk between them.
+///
+/// Valves are numbered `0..len()` here, which is also their bit in a [`ValveSet`].
+/// The start gets index `len()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Valves {
+    /// The node each valve was in the original graph.
+    pub nodes: Vec<Node>,
+    pub flows: Vec<i64>,
+    pub start: Node,
+    /// Walking distances between the valves, and from the start.
+    pub distances: APSP,
+}
+
+impl Graph {
+    /// Drop every valve that isn't worth opening, only keeping
+    /// how far apart the rest (and `start`) are.
+    pub fn compress(&self, distances: &APSP, start: Node) -> Valves {
+        let nodes = self.non_zero_flow_indices.clone();
+        let flows = nodes.iter().map(|node| self.flow_of(node).unwrap() as i64).collect();
+
+        let with_start = nodes.iter().chain([&start]).map(|&node| node as usize).collect::<Vec<_>>();
+        let distances = distances.restrict(&with_start);
+
+        Valves { nodes, flows, start, distances }
+    }
+}
+
+impl Valves {
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Index of the start, for [`Valves::distance`] and [`Valves::visit`].
+    pub fn start_index(&self) -> usize {
+        self.len()
+    }
+
+    /// `i64::MAX` if there's no way from one to the other.
+    pub fn distance(&self, from: usize, to: usize) -> i64 {
+        match self.distances[(from, to)] {
+            APSP::UNREACHABLE => i64::MAX,
+            distance => distance as i64,
+        }
+    }
+
+    /// Thanks to [JuniorBirdman1115's Reddit post],
+    /// the key insight is to store the visited state as a bitset.
+    /// Record the best flow achievable for every set of opened valves in `answer`,
+    /// and the route that gets it in `routes` if given.
+    /// Minutes in the routes count from when this search starts.
+    ///
+    /// [JuniorBirdman1115's Reddit post]: https://www.reddit.com/r/adventofcode/comments/zn6k1l/comment/j0oo5a9/
+    #[allow(clippy::too_many_arguments)]
+    pub fn visit<S: ValveSet>(
+        &self,
+        current: usize,
+        budget: i64,
+        state: S,
+        flow: i64,
+        route: &mut Vec<Opening>,
+        answer: &mut HashMap<S, i64>,
+        mut routes: Option<&mut HashMap<S, Schedule>>,
+    ) {
+        // Update our cache if a better flow is achieved.
+        if answer.get(&state).is_none_or(|&best| flow > best) {
+            if let Some(routes) = routes.as_deref_mut() {
+                routes.insert(state.clone(), Schedule { openings: route.clone() });
+            }
+            answer.insert(state.clone(), flow);
+        }
+
+        for next in 0..self.len() {
+            let distance = self.distance(current, next);
+            if state.contains(next) || distance == i64::MAX {
+                continue;
+            }
+
+            // Walk there, then spend a minute turning the valve on.
+            let new_budget = budget - (distance + 1);
+            if new_budget <= 0 {
+                continue;
+            }
+
+            let minute = route.last().map_or(0, |opening| opening.minute) + distance + 1;
+            route.push(Opening { valve: self.nodes[next], minute, flow: self.flows[next] });
+            self.visit(
+                next,
+                new_budget,
+                state.with(next),
+                flow + new_budget * self.flows[next],
+                route,
+                answer,
+                routes.as_deref_mut(),
+            );
+            route.pop();
+        }
+    }
+
+    /// The best flow for every set of valves one agent can open in time.
+    pub fn best_flows<S: ValveSet>(&self, budget: i64) -> HashMap<S, i64> {
+        let mut answer = HashMap::new();
+        let state = S::empty(self.len());
+        self.visit(self.start_index(), budget, state, 0, &mut vec![], &mut answer, None);
+        answer
+    }
+
+    /// The most one agent can release, with a set just big enough for the number of valves.
+    pub fn max_release(&self, budget: i64) -> i64 {
+        fn best<S: ValveSet>(valves: &Valves, budget: i64) -> i64 {
+            valves.best_flows::<S>(budget).into_values().max().unwrap_or(0)
+        }
+
+        match self.len() {
+            0..=64 => best::<u64>(self, budget),
+            65..=128 => best::<u128>(self, budget),
+            _ => best::<DynamicSet>(self, budget),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_input_graph;
+    use crate::test_support::corridor;
+
+    #[test]
+    fn every_set_agrees_on_the_real_input() {
+        let graph = get_input_graph(include_str!("input.txt"));
+        let distances = graph.all_pairs_shortest_paths();
+        let valves = graph.compress(&distances, graph.index_of("AA").unwrap());
+        assert_eq!(valves.len(), 15);
+
+        let best = |flows: Vec<i64>| flows.into_iter().max().unwrap();
+        assert_eq!(best(valves.best_flows::<u64>(30).into_values().collect()), 1940);
+        assert_eq!(best(valves.best_flows::<u128>(30).into_values().collect()), 1940);
+        assert_eq!(best(valves.best_flows::<DynamicSet>(30).into_values().collect()), 1940);
+    }
+
+    #[test]
+    fn scales_past_a_byte_of_nodes() {
+        let graph = get_input_graph(&corridor(300));
+        let distances = graph.shortest_paths_bfs();
+        let valves = graph.compress(&distances, graph.index_of("AAA").unwrap());
+        assert_eq!(valves.len(), 299);
+
+        // The nearest valves first, one every two minutes: 8 + 6 + 4 + 2.
+        assert_eq!(valves.max_release(10), 20);
+    }
+
+    #[test]
+    fn picks_a_set_that_fits() {
+        for (len, budget, expected) in [(60, 6, 4 + 2), (100, 8, 6 + 4 + 2)] {
+            let graph = get_input_graph(&corridor(len));
+            let distances = graph.shortest_paths_bfs();
+            let valves = graph.compress(&distances, graph.index_of("AAA").unwrap());
+            assert_eq!(valves.max_release(budget), expected);
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{Cave, Direction};
+
+
+/// Something that happened to the falling rock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Event {
+    /// The next of the [`Rocks`](crate::Rocks) appeared, with its bottom left corner at `bottom_left`.
+    Spawned { shape: usize, bottom_left: (isize, isize) },
+    /// A jet pushed the rock, which only `moved` if nothing was in the way.
+    Pushed { direction: Direction, moved: bool },
+    /// The rock fell one unit.
+    Fell,
+    /// The rock couldn't fall any further, leaving the tower `height` tall.
+    Rested { height: usize },
+}
+
+/// What the puzzle says about an event, followed by a picture of the cave.
+pub fn narrate(cave: &Cave, event: Event) -> String {
+    let direction = |direction| match direction {
+        Direction::Left => "left",
+        Direction::Right => "right",
+        Direction::Down => "down",
+    };
+    let line = match event {
+        Event::Spawned { .. } => "A new rock begins falling:".to_string(),
+        Event::Pushed { direction: jet, moved: true } => format!("Jet of gas pushes rock {}:", direction(jet)),
+        Event::Pushed { direction: jet, moved: false } => {
+            format!("Jet of gas pushes rock {}, but nothing happens:", direction(jet))
+        },
+        Event::Fell => "Rock falls 1 unit:".to_string(),
+        Event::Rested { .. } => "Rock falls 1 unit, causing it to come to rest:".to_string(),
+    };
+    format!("{}\n{}", line, cave)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const EXAMPLE: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+    #[test]
+    fn first_rock_like_the_puzzle() {
+        let mut cave = Cave::new(get_directions(EXAMPLE), 7).unwrap();
+        let mut events = vec![];
+        cave.drop_rock_with(&mut |_, event| events.push(event));
+
+        assert_eq!(events, vec![
+            Event::Spawned { shape: RockKind::Minus as usize, bottom_left: (3, 2) },
+            Event::Pushed { direction: Direction::Right, moved: true },
+            Event::Fell,
+            Event::Pushed { direction: Direction::Right, moved: false },
+            Event::Fell,
+            Event::Pushed { direction: Direction::Right, moved: false },
+            Event::Fell,
+            Event::Pushed { direction: Direction::Left, moved: true },
+            Event::Rested { height: 1 },
+        ]);
+    }
+
+    #[test]
+    fn narration_is_one_consumer() {
+        let mut cave = Cave::new(get_directions(EXAMPLE), 7).unwrap();
+        let mut narration = vec![];
+        cave.drop_rock_with(&mut |cave, event| narration.push(narrate(cave, event)));
+
+        assert_eq!(narration[0], "A new rock begins falling:
+|..@@@@.|
+|.......|
+|.......|
+|.......|
++-------+
+");
+        assert_eq!(narration[3], "Jet of gas pushes rock right, but nothing happens:
+|...@@@@|
+|.......|
+|.......|
++-------+
+");
+        assert_eq!(narration[8], "Rock falls 1 unit, causing it to come to rest:
+|..####.|
++-------+
+");
+    }
+
+    #[test]
+    fn events_add_up() {
+        let mut cave = Cave::new(get_directions(EXAMPLE), 7).unwrap();
+        let (mut spawned, mut rested, mut last_height) = (0, 0, 0);
+        cave.run_with(2022, |cave, event| match event {
+            Event::Spawned { .. } => spawned += 1,
+            Event::Rested { height } => {
+                rested += 1;
+                last_height = height;
+                assert_eq!(height, cave.height());
+            },
+            _ => {},
+        });
+        assert_eq!((spawned, rested, last_height), (2022, 2022, 3068));
+    }
+}
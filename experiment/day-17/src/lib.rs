@@ -1,8 +1,12 @@
+mod event;
 mod parse;
-use std::{collections::{HashSet, HashMap}, cmp::Reverse};
+mod shape;
+use advent::Solution;
+use std::collections::HashMap;
 
-use indicatif::ProgressBar;
+pub use event::*;
 pub use parse::*;
+pub use shape::*;
 
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -27,6 +31,13 @@ impl Iterator for Directions {
     }
 }
 
+impl Directions {
+    /// Index of the jet that pushes next.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
 impl From<Vec<Direction>> for Directions {
     fn from(inner: Vec<Direction>) -> Self {
         Self { inner, index: 0 }
@@ -34,17 +45,18 @@ impl From<Vec<Direction>> for Directions {
 }
 
 
+/// Measured from its bottom left, so the smallest row and column are always 0.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Rock {
-    pub col_min: u8,
     pub col_max: u8,
-    pub row_min: u8,
     pub row_max: u8,
     pub filled: Vec<(u8, u8)>,
-    pub empty_space_required: HashMap<Direction, Vec<(i8, i8)>>
+    /// One mask per row from the bottom up, with bit `col` set where the rock is.
+    pub masks: Vec<u64>,
 }
 
 
+/// The rocks from the puzzle.
 #[derive(Debug, Clone, PartialEq, Eq, Copy, Hash)]
 pub enum RockKind {
     Minus,
@@ -59,316 +71,345 @@ impl Rock {
     /// with going right implying an increase in (.1)
     /// and going down implying an increase in (.0)
     pub fn new(kind: RockKind) -> Self {
-        match kind {
-            RockKind::Minus => {
-                Self {
-                    col_min: 0,
-                    col_max: 3,
-                    row_min: 0,
-                    row_max: 0,
-                    filled: vec![(0, 0), (0, 1), (0, 2), (0, 3)],
-                    empty_space_required: HashMap::from_iter(vec![
-                        (Direction::Left, vec![(0, -1)]),
-                        (Direction::Right, vec![(0, 4)]),
-                        (Direction::Down, vec![(-1, 0), (-1, 1), (-1, 2), (-1, 3)])
-                    ].into_iter())
-                }
-            },
-            RockKind::Plus => {
-                Self {
-                    col_min: 0,
-                    col_max: 2,
-                    row_min: 0,
-                    row_max: 2,
-                    filled: vec![(0, 1), (1, 0), (1, 1), (1, 2), (2, 1)],
-                    empty_space_required: HashMap::from_iter(vec![
-                        (Direction::Left, vec![(1, -1), (0, 0), (2, 0)]),
-                        (Direction::Right, vec![(1, 3), (0, 2), (2, 2)]),
-                        (Direction::Down, vec![(0, 0), (-1, 1), (0, 2)])
-                    ].into_iter())
-                }
-            },
-            RockKind::MirrorL => {
-                Self {
-                    col_min: 0,
-                    col_max: 2,
-                    row_min: 0,
-                    row_max: 2,
-                    filled: vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)],
-                    empty_space_required: HashMap::from_iter(vec![
-                        (Direction::Left, vec![(0, -1), (1, 1), (2, 1)]),
-                        (Direction::Right, vec![(0, 3), (1, 3), (2, 3)]),
-                        (Direction::Down, vec![(-1, 0), (-1, 1), (-1, 2)])
-                    ].into_iter())
-                }
-            },
-            RockKind::Vertical => {
-                Self {
-                    col_min: 0,
-                    col_max: 0,
-                    row_min: 0,
-                    row_max: 3,
-                    filled: vec![(0, 0), (1, 0), (2, 0), (3, 0)],
-                    empty_space_required: HashMap::from_iter(vec![
-                        (Direction::Left, vec![(0, -1), (1, -1), (2, -1), (3, -1)]),
-                        (Direction::Right, vec![(0, 1), (1, 1), (2, 1), (3, 1)]),
-                        (Direction::Down, vec![(-1, 0)])
-                    ].into_iter())
-                }
-            },
-            RockKind::Square => {
-                Self {
-                    col_min: 0,
-                    col_max: 1,
-                    row_min: 0,
-                    row_max: 1,
-                    filled: vec![(0, 0), (0, 1), (1, 0), (1, 1)],
-                    empty_space_required: HashMap::from_iter(vec![
-                        (Direction::Left, vec![(0, -1), (1, -1)]),
-                        (Direction::Right, vec![(0, 2), (1, 2)]),
-                        (Direction::Down, vec![(-1, 0), (-1, 1)])
-                    ].into_iter())
-                }
-            }
-        }
+        Self::from_art(kind.art()).expect("The puzzle's rocks are well drawn.")
     }
-
-    // pub fn can_move(&self, direction: Direction) -> bool {
-    //     let positions = self.empty_space_required.get(&direction).unwrap();
-
-    //     // match direction {
-    //     //     Direction::Left => {
-
-    //     //     },
-    //     //     Direction::Right => {
-
-    //     //     },
-    //     //     Direction::Down => {
-
-    //     //     }
-    //     // };
-    //     false
-    // }
 }
 
 
+/// The rocks that fall, over and over again in the same order,
+/// along with where each one is in the order.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct RockKindIterator {
-    inner: Vec<RockKind>,
+pub struct Rocks {
+    shapes: Vec<Rock>,
     index: usize
 }
 
-impl RockKindIterator {
+impl Default for Rocks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rocks {
+    /// The puzzle's five rocks.
     pub fn new() -> Self {
         Self {
-            inner: vec![
-                RockKind::Minus,
-                RockKind::Plus,
-                RockKind::MirrorL,
-                RockKind::Vertical,
-                RockKind::Square
-            ],
+            shapes: RockKind::ALL.iter().map(|&kind| Rock::new(kind)).collect(),
             index: 0
         }
     }
-}
 
-
-impl Iterator for RockKindIterator {
-    type Item = RockKind;
-    fn next(&mut self) -> Option<Self::Item> {
-        let prev = self.inner[self.index];
-        self.index = (self.index + 1) % self.inner.len();
-        Some(prev)
+    pub fn from_shapes(shapes: Vec<Rock>) -> Result<Self, ShapeError> {
+        match shapes.is_empty() {
+            true => Err(ShapeError::NoShapes),
+            false => Ok(Self { shapes, index: 0 }),
+        }
     }
-}
-
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Rocks {
-    rock_kind_iterator: RockKindIterator
-}
 
-impl Rocks {
-    pub fn new() -> Self {
-        Self {
-            rock_kind_iterator: RockKindIterator::new()
-        }
+    pub fn shapes(&self) -> &[Rock] {
+        &self.shapes
     }
 }
 
 impl Iterator for Rocks {
-    type Item = (Rock, RockKind);
+    type Item = (Rock, usize);
     fn next(&mut self) -> Option<Self::Item> {
-        self
-        .rock_kind_iterator
-        .next()
-        .and_then(|kind| Some((Rock::new(kind), kind)))
+        let shape = self.index;
+        self.index = (self.index + 1) % self.shapes.len();
+        Some((self.shapes[shape].clone(), shape))
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct ActiveRock {
     pub rock: Rock,
-    pub kind: RockKind,
+    /// Which of the [`Rocks`] this is.
+    pub shape: usize,
     pub bottom_left: (isize, isize),
 }
 
+/// Where new rocks appear: `left` units away from the left wall,
+/// and `above` units above the highest rock or the floor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Spawn {
+    pub left: usize,
+    pub above: usize,
+}
+
+impl Default for Spawn {
+    fn default() -> Self {
+        Self { left: 2, above: 3 }
+    }
+}
+
+/// What the cave looks like right after a rock comes to rest.
+/// Once the same fingerprint shows up twice, everything in between repeats forever.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Fingerprint {
+    pub shape: usize,
+    pub jet_index: usize,
+    /// How far below the top of the tower the highest rock in each column is,
+    /// capped at `Cave::surface_rows`.
+    pub surface: Vec<usize>,
+}
+
+/// The state after `start` rocks repeats after every `length` more rocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct Cave {
     rocks: Rocks,
     directions: Directions,
-    pub width: usize,
-    existing_filled: HashSet<(isize, isize)>,
+    width: usize,
+    spawn: Spawn,
+    /// The rocks that came to rest, `stride` bytes per row from the floor up,
+    /// with bit `col % 8` of byte `col / 8` set where that column is filled.
+    rows: Vec<u8>,
+    stride: usize,
     active_rock: Option<ActiveRock>,
-    spawn_position: Option<(isize, isize)>,
     rocks_stabilized: usize,
-    pub quiet: bool,
-    pub log_spawn: bool,
-    // pub height_deltas: HashMap<(RockKind, Direction, isize), Vec<isize>>
+    /// How many rows below the top make up the surface in a [`Fingerprint`].
+    pub surface_rows: usize,
+    /// The height of the tower after every rock so far, starting at no rocks.
+    pub heights: Vec<usize>,
+    /// When each fingerprint was first seen, as a number of rocks.
+    pub fingerprints: HashMap<Fingerprint, usize>,
+    pub cycle: Option<Cycle>,
+    top: usize,
 }
 
 impl Cave {
-    pub fn new(directions: Directions, width: usize) -> Self {
-        Self {
-            rocks: Rocks::new(),
+    /// A cave for the puzzle's rocks, spawned the puzzle's way.
+    pub fn new(directions: Directions, width: usize) -> Result<Self, ShapeError> {
+        Self::with_rocks(directions, width, Rocks::new(), Spawn::default())
+    }
+
+    /// Every one of `rocks` has to fit between the walls where it spawns,
+    /// which is checked once here rather than every time one spawns.
+    pub fn with_rocks(directions: Directions, width: usize, rocks: Rocks, spawn: Spawn) -> Result<Self, ShapeError> {
+        let misfit = rocks.shapes().iter().position(|rock| spawn.left + rock.col_max as usize >= width);
+        if let Some(shape) = misfit {
+            return Err(ShapeError::DoesNotFit { shape, width });
+        }
+
+        Ok(Self {
+            rocks,
             directions,
             width,
-            existing_filled: HashSet::new(),
+            spawn,
+            rows: vec![],
+            stride: width.div_ceil(8).max(1),
             active_rock: None,
-            spawn_position: Some((3, 2)),
             rocks_stabilized: 0,
-            quiet: true,
-            log_spawn: true,
-            // height_deltas: HashMap::new()
-        }
+            surface_rows: 32,
+            heights: vec![0],
+            fingerprints: HashMap::new(),
+            cycle: None,
+            top: 0,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
     }
 
     pub fn height(&self) -> usize {
-        self.existing_filled.iter().map(|(x, _)| *x).max().unwrap_or(0) as usize + 1
+        self.top
+    }
+
+    pub fn rocks_stabilized(&self) -> usize {
+        self.rocks_stabilized
     }
 
     pub fn run(&mut self, max_stabilized_rocks: usize) {
-        let progress_bar = ProgressBar::new(max_stabilized_rocks as u64);
-        loop {
-            if self.active_rock.is_none() {
-                // No active rock. Spawn a new one.
-                let (rock, kind) = self.rocks.next().unwrap();
-                self.active_rock = Some(ActiveRock {
-                    rock,
-                    kind,
-                    bottom_left: self.spawn_position.unwrap()
-                });
-
-                if self.log_spawn || !self.quiet {
-                    println!("A new rock begins falling!:");
-                    println!("{}", self);
-                }
-            }
+        self.run_with(max_stabilized_rocks, |_, _| {});
+    }
 
-            let jet_direction = self.directions.next().unwrap();
-            let mut active_rock = self.active_rock.clone().unwrap();
-            
-            let empty_spaces_required = active_rock.rock.empty_space_required.get(&jet_direction).unwrap();
-            
-            let all_are_unoccupied = empty_spaces_required
-            .iter()
-            .all(
-                |(delta_row, delta_col)| {
-                    let position = (active_rock.bottom_left.0 + *delta_row as isize, active_rock.bottom_left.1 + *delta_col as isize);
-                    !self.is_occupied(position)
-                }
-            );
-
-            if all_are_unoccupied {
-                // now move the active rock left/right.
-                match jet_direction {
-                    Direction::Left => {
-                        active_rock.bottom_left = (active_rock.bottom_left.0, active_rock.bottom_left.1 - 1);
-                    },
-                    Direction::Right => {
-                        active_rock.bottom_left = (active_rock.bottom_left.0, active_rock.bottom_left.1 + 1);
-                    },
-                    _ => unreachable!("Should not be able to move down due to jet.")
-                }
-                self.active_rock = Some(active_rock.clone());
-                if !self.quiet {
-                    println!("Jet of gas pushes rock {:#?}:", jet_direction);
-                    println!("{}", self);
-                }
-            } else {
-                if !self.quiet {
-                    println!("Jet of gas pushes rock {:#?}, but nothing happens:", jet_direction);
-                    println!("{}", self);
+    /// Like [`Cave::run`], telling `on_event` about everything that happens along the way.
+    pub fn run_with<F: FnMut(&Cave, Event)>(&mut self, max_stabilized_rocks: usize, mut on_event: F) {
+        while self.rocks_stabilized < max_stabilized_rocks {
+            self.drop_rock_with(&mut on_event);
+        }
+    }
+
+    /// Keep going until the current rock comes to rest.
+    pub fn drop_rock(&mut self) {
+        self.drop_rock_with(&mut |_, _| {});
+    }
+
+    pub fn drop_rock_with<F: FnMut(&Cave, Event)>(&mut self, on_event: &mut F) {
+        while !self.step_with(on_event) {}
+    }
+
+    /// Spawn a rock if needed, then push it with the next jet and let it fall one unit.
+    /// Returns whether that made it come to rest.
+    pub fn step(&mut self) -> bool {
+        self.step_with(&mut |_, _| {})
+    }
+
+    /// Like [`Cave::step`], calling `on_event` with the cave as it is right after each event.
+    pub fn step_with<F: FnMut(&Cave, Event)>(&mut self, on_event: &mut F) -> bool {
+        if self.active_rock.is_none() {
+            // No active rock. Spawn a new one.
+            let (rock, shape) = self.rocks.next().unwrap();
+            let bottom_left = ((self.top + self.spawn.above) as isize, self.spawn.left as isize);
+            self.active_rock = Some(ActiveRock {
+                rock,
+                shape,
+                bottom_left
+            });
+            on_event(self, Event::Spawned { shape, bottom_left });
+        }
+
+        let jet_direction = self.directions.next().unwrap();
+        let mut active_rock = self.active_rock.take().unwrap();
+        let (row, col) = active_rock.bottom_left;
+
+        let pushed_to = match jet_direction {
+            Direction::Left => (row, col - 1),
+            Direction::Right => (row, col + 1),
+            _ => unreachable!("Should not be able to move down due to jet.")
+        };
+        let moved = self.fits(&active_rock.rock, pushed_to);
+        if moved {
+            active_rock.bottom_left = pushed_to;
+        }
+        self.active_rock = Some(active_rock);
+        on_event(self, Event::Pushed { direction: jet_direction, moved });
+
+        // Now time to move the rock down one unit, if we can.
+        let mut active_rock = self.active_rock.take().unwrap();
+        let (row, col) = active_rock.bottom_left;
+
+        if self.fits(&active_rock.rock, (row - 1, col)) {
+            active_rock.bottom_left = (row - 1, col);
+            self.active_rock = Some(active_rock);
+            on_event(self, Event::Fell);
+            return false;
+        }
+
+        // We cannot move down. This rock is now stabilized.
+        self.rocks_stabilized += 1;
+        self.settle(&active_rock);
+        self.heights.push(self.top);
+
+        if self.cycle.is_none() {
+            let fingerprint = self.fingerprint(active_rock.shape);
+            match self.fingerprints.get(&fingerprint) {
+                Some(&start) => {
+                    self.cycle = Some(Cycle { start, length: self.rocks_stabilized - start });
+                },
+                None => {
+                    self.fingerprints.insert(fingerprint, self.rocks_stabilized);
                 }
             }
+        }
 
-            // Now time to move the rock down one unit, if we can.
-            let can_move_down = 
-                active_rock
-                .rock
-                .empty_space_required
-                .get(&Direction::Down)
-                .unwrap()
-                .iter()
-                .all(
-                    |(delta_row, delta_col)| {
-                        let position = (active_rock.bottom_left.0 + *delta_row as isize, active_rock.bottom_left.1 + *delta_col as isize);
-                        !self.is_occupied(position)
-                    }
-                );
-
-            if can_move_down {
-                active_rock.bottom_left = (active_rock.bottom_left.0 - 1, active_rock.bottom_left.1);
-                self.active_rock = Some(active_rock.clone());
-                if !self.quiet {
-                    println!("Rock falls 1 unit:");
-                    println!("{}", self);
-                }
-            } else {
-                // We cannot move down. This rock is now stabilized.
-                // Add it to the existing filled.
-                self.rocks_stabilized += 1;
-                progress_bar.inc(1);
-                // let highest_placed_rock_so_far = self.existing_filled.iter().max_by_key(|x| x.0).unwrap_or(&(0, 0)).0;
-
-
-                active_rock.rock.filled.iter().for_each(|(p_row, p_col)| {
-                    let position = (active_rock.bottom_left.0 + *p_row as isize, active_rock.bottom_left.1 + *p_col as isize);
-                    self.existing_filled.insert(position);
-                });
-                // Tricky stuff. The jet could potentially maneuver the rock
-                // much lower than the previous max.
-                let highest_placed_rock = self.existing_filled.iter().max_by_key(|x| x.0).unwrap().0;
-
-                // let height_delta = highest_placed_rock - highest_placed_rock_so_far;
-                // self.height_deltas.entry((active_rock.kind, jet_direction, height_delta)).and_modify(|e| e.push(self.rocks_stabilized as isize)).or_insert_with(|| vec![self.rocks_stabilized as isize]);
-
-                self.spawn_position = Some(
-                    (
-                        highest_placed_rock + 4, 
-                        2
-                    )
-                );
-                self.active_rock = None;
-
-                if !self.quiet {
-                    println!("Rock falls 1 unit, causing it to come to rest:");
-                    println!("{}", self);
+        on_event(self, Event::Rested { height: self.top });
+        true
+    }
+
+    /// Whether the rock would be clear of the walls, the floor and every settled rock
+    /// with its bottom left corner at `(row, col)`.
+    pub fn fits(&self, rock: &Rock, (row, col): (isize, isize)) -> bool {
+        if row < 0 || col < 0 || col as usize + rock.col_max as usize >= self.width {
+            return false;
+        }
+        rock.masks.iter().enumerate().all(|(offset, &mask)| {
+            let Some(settled) = self.row(row as usize + offset) else {
+                return true;
+            };
+            // The mask can straddle a few bytes once it's shifted into place.
+            let mut shifted = (mask as u128) << (col % 8);
+            let mut byte = col as usize / 8;
+            while shifted != 0 {
+                if settled[byte] & shifted as u8 != 0 {
+                    return false;
                 }
+                shifted >>= 8;
+                byte += 1;
             }
+            true
+        })
+    }
+
+    /// The bytes of a row, if anything has come to rest that high up yet.
+    fn row(&self, row: usize) -> Option<&[u8]> {
+        self.rows.get(row * self.stride..(row + 1) * self.stride)
+    }
 
-            if self.rocks_stabilized == max_stabilized_rocks {
-                break;
+    fn row_mut(&mut self, row: usize) -> &mut [u8] {
+        if (row + 1) * self.stride > self.rows.len() {
+            self.rows.resize((row + 1) * self.stride, 0);
+        }
+        &mut self.rows[row * self.stride..(row + 1) * self.stride]
+    }
+
+    /// Fill in the rock where it is, and raise the top of the tower if it's sticking out.
+    /// The jet could maneuver a rock well below the top before it comes to rest.
+    fn settle(&mut self, active_rock: &ActiveRock) {
+        let (row, col) = active_rock.bottom_left;
+        for (offset, &mask) in active_rock.rock.masks.iter().enumerate() {
+            let settled = self.row_mut(row as usize + offset);
+            let mut shifted = (mask as u128) << (col % 8);
+            let mut byte = col as usize / 8;
+            while shifted != 0 {
+                settled[byte] |= shifted as u8;
+                shifted >>= 8;
+                byte += 1;
             }
         }
+        self.top = self.top.max(row as usize + active_rock.rock.masks.len());
+    }
+
+    /// Fill in a single cell, as if a rock had come to rest there.
+    pub fn fill(&mut self, (row, col): (isize, isize)) {
+        let (row, col) = (row as usize, col as usize);
+        self.row_mut(row)[col / 8] |= 1 << (col % 8);
+        self.top = self.top.max(row + 1);
+    }
+
+    pub fn fingerprint(&self, shape: usize) -> Fingerprint {
+        let top = self.top as isize;
+        let surface = (0..self.width as isize)
+            .map(|col| {
+                (0..self.surface_rows)
+                    .find(|&depth| self.is_occupied((top - 1 - depth as isize, col)))
+                    .unwrap_or(self.surface_rows)
+            })
+            .collect();
+
+        Fingerprint {
+            shape,
+            jet_index: self.directions.index(),
+            surface,
+        }
+    }
+
+    /// The height of the tower after `num_rocks` rocks, either from what we've
+    /// already dropped or by repeating the cycle, once we've found one.
+    pub fn extrapolate_height(&self, num_rocks: usize) -> Option<usize> {
+        if let Some(&height) = self.heights.get(num_rocks) {
+            return Some(height);
+        }
+        let Cycle { start, length } = self.cycle?;
+        let per_cycle = self.heights[start + length] - self.heights[start];
+        let cycles = (num_rocks - start) / length;
+        let offset = (num_rocks - start) % length;
+
+        Some(self.heights[start + offset] + cycles * per_cycle)
     }
 
     pub fn is_occupied(&self, (row, col): (isize, isize)) -> bool {
         if col < 0 || col >= self.width as isize || row < 0 {
             return true;
         }
-        self.existing_filled.contains(&(row, col))
+        let (row, col) = (row as usize, col as usize);
+        self.row(row).is_some_and(|filled| filled[col / 8] & (1 << (col % 8)) != 0)
     }
 
 
@@ -379,25 +420,16 @@ use std::fmt::Display;
 impl Display for Cave {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         
-        let lowest_col = 0 as isize;
+        let lowest_col = 0_isize;
         let highest_col = self.width as isize;
         let lowest_row = 0;
         let highest_row = {
             // First check the existing stabilized ones.
-            let mut highest = 
-                self
-                .existing_filled
-                .iter()
-                .max_by_key(
-                    |(row, _)| *row
-                )
-                // .map(|(row, _)| *row)
-                .unwrap_or(&(0, 0)).0;
+            let mut highest = (self.top as isize - 1).max(0);
             
             // Now if there's an active_rock, check that too.
             if let Some(active_rock) = &self.active_rock {
                 let (row, _) = active_rock.bottom_left;
-                let row = row as isize;
                 highest = highest.max(row + active_rock.rock.row_max as isize);
             }
 
@@ -427,8 +459,6 @@ impl Display for Cave {
         // Get indices of an active rock, if exist.
         let active_rock_indices = self.active_rock.as_ref().map(|active_rock| {
             let (row, col) = active_rock.bottom_left;
-            let row = row as isize;
-            let col = col as isize;
             active_rock.rock.filled.iter().map(|(r, c)| (row + *r as isize, col + *c as isize)).collect::<Vec<_>>()
         }).unwrap_or(vec![]);
 
@@ -443,13 +473,65 @@ impl Display for Cave {
 
         let contents = results.join("\n");
 
-        write!(f, "{}\n", contents)
+        writeln!(f, "{}", contents)
     }
 }
 
+
+pub fn get_directions(s: &str) -> Directions {
+    parse_direction(s).unwrap().1.into()
+}
+
+
+pub fn solve_part1(
+    directions: Directions, 
+    num_rocks: usize
+) -> usize {
+
+    let mut cave = Cave::new(directions, 7).expect("The puzzle's rocks fit a cave 7 wide.");
+    cave.run(num_rocks);
+
+    cave.height()
+}
+
+/// Way too many rocks to drop one by one, but the tower eventually
+/// repeats itself. Drop rocks until it does, then skip ahead.
+pub fn solve_part2(
+    directions: Directions,
+    num_rocks: usize
+) -> usize {
+    let mut cave = Cave::new(directions, 7).expect("The puzzle's rocks fit a cave 7 wide.");
+
+    while cave.cycle.is_none() && cave.rocks_stabilized < num_rocks {
+        cave.drop_rock();
+    }
+    cave.extrapolate_height(num_rocks).unwrap()
+}
+
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Directions;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Self::Input {
+        get_directions(input.trim())
+    }
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        solve_part1(input.clone(), 2022)
+    }
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        solve_part2(input.clone(), 1_000_000_000_000)
+    }
+}
+
+
 #[cfg(test)]
 mod tests {
     use crate::*;
+    use std::collections::HashSet;
 
     #[test]
     fn test_directions() {
@@ -467,26 +549,26 @@ mod tests {
     fn test_rocks() {
         let mut rocks = Rocks::new();
 
-        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Minus), RockKind::Minus)));
-        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Plus), RockKind::Plus)));
-        assert_eq!(rocks.next(), Some((Rock::new(RockKind::MirrorL), RockKind::MirrorL)));
-        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Vertical), RockKind::Vertical)));
-        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Square), RockKind::Square)));
-        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Minus), RockKind::Minus)));
-        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Plus), RockKind::Plus)));
-        assert_eq!(rocks.next(), Some((Rock::new(RockKind::MirrorL), RockKind::MirrorL)));
-        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Vertical), RockKind::Vertical)));
-        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Square), RockKind::Square)));
-        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Minus), RockKind::Minus)));
+        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Minus), RockKind::Minus as usize)));
+        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Plus), RockKind::Plus as usize)));
+        assert_eq!(rocks.next(), Some((Rock::new(RockKind::MirrorL), RockKind::MirrorL as usize)));
+        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Vertical), RockKind::Vertical as usize)));
+        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Square), RockKind::Square as usize)));
+        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Minus), RockKind::Minus as usize)));
+        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Plus), RockKind::Plus as usize)));
+        assert_eq!(rocks.next(), Some((Rock::new(RockKind::MirrorL), RockKind::MirrorL as usize)));
+        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Vertical), RockKind::Vertical as usize)));
+        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Square), RockKind::Square as usize)));
+        assert_eq!(rocks.next(), Some((Rock::new(RockKind::Minus), RockKind::Minus as usize)));
     }
 
     #[test]
     fn test_cave() {
         let directions = vec![Direction::Left, Direction::Right];
         let directions = super::Directions::from(directions);
-        let mut cave = Cave::new(directions, 7);
-        assert_eq!(cave.width, 7);
-        let hset: HashSet<(isize, isize)> = HashSet::from_iter(vec![
+        let mut cave = Cave::new(directions, 7).unwrap();
+        assert_eq!(cave.width(), 7);
+        let filled: HashSet<(isize, isize)> = HashSet::from_iter(vec![
             (0, 2),
             (0, 3),
             (0, 4),
@@ -497,11 +579,13 @@ mod tests {
             (2, 4),
             (3, 3),
         ]);
-        cave.existing_filled = hset;
+        for position in filled {
+            cave.fill(position);
+        }
 
         let active_rock = ActiveRock {
             rock: Rock::new(RockKind::MirrorL),
-            kind: RockKind::MirrorL,
+            shape: RockKind::MirrorL as usize,
             bottom_left: (7, 3)
         };
         cave.active_rock = Some(active_rock);
@@ -515,17 +599,134 @@ mod tests {
         // let input = include_str!("input.txt");
         let directions = parse_direction(input).unwrap().1;
         let directions = super::Directions::from(directions);
-        let mut cave = Cave::new(directions, 7);
-        cave.quiet = true;
-        cave.log_spawn = false;
+        let mut cave = Cave::new(directions, 7).unwrap();
         cave.run(2022);
 
+        assert_eq!(cave.rocks_stabilized(), 2022);
+        assert_eq!(cave.heights.len(), 2023);
+        assert_eq!(cave.height(), 3068);
+        assert!(cave.cycle.is_some());
+    }
+
+
+    #[test]
+    fn test_smol_part1() {
+        let s = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+        let directions = get_directions(s);
+        let result = solve_part1(directions, 2022);
+        assert_eq!(result, 3068);
+    }
+
+    #[test]
+    fn test_smol_part2() {
+        let s = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+        let directions = get_directions(s);
+        assert_eq!(solve_part2(directions.clone(), 2022), 3068);
+        assert_eq!(solve_part2(directions, 1_000_000_000_000), 1514285714288);
+    }
+
+    #[test]
+    fn test_cycle_matches_simulation() {
+        let directions = get_directions(include_str!("input.txt").trim());
+        let mut cave = Cave::new(directions.clone(), 7).unwrap();
+        cave.run(10_000);
+
+        assert_eq!(solve_part2(directions.clone(), 2022), 3184);
+        assert_eq!(solve_part2(directions, 10_000), cave.height());
+    }
+
+    #[test]
+    fn cycle_skips_ahead_like_the_simulation() {
+        let directions = get_directions(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>");
+        let mut cave = Cave::new(directions.clone(), 7).unwrap();
+        cave.run(50_000);
+
+        assert_eq!(solve_part2(directions, 50_000), cave.height());
+    }
+
+    #[test]
+    #[ignore = "drops a million rocks one at a time, which is slow in debug builds"]
+    fn a_million_rocks() {
+        let directions = get_directions(include_str!("input.txt").trim());
+        let mut cave = Cave::new(directions.clone(), 7).unwrap();
+        cave.run(1_000_000);
+
+        assert_eq!(cave.heights.len(), 1_000_001);
+        assert_eq!(solve_part2(directions, 1_000_000), cave.height());
+    }
+
+    /// The way the cave used to work: every cell in a set, and
+    /// a rock moves if every cell it would move into is clear.
+    fn naive_heights(mut rocks: Rocks, mut directions: Directions, width: isize, spawn: Spawn, num_rocks: usize) -> Vec<usize> {
+        let mut filled: HashSet<(isize, isize)> = HashSet::new();
+        let mut heights = vec![0];
+        let clear = |filled: &HashSet<(isize, isize)>, rock: &Rock, (row, col): (isize, isize)| {
+            rock.filled.iter().all(|&(delta_row, delta_col)| {
+                let (row, col) = (row + delta_row as isize, col + delta_col as isize);
+                row >= 0 && (0..width).contains(&col) && !filled.contains(&(row, col))
+            })
+        };
+        for _ in 0..num_rocks {
+            let (rock, _) = rocks.next().unwrap();
+            let top = *heights.last().unwrap();
+            let (mut row, mut col) = ((top + spawn.above) as isize, spawn.left as isize);
+            loop {
+                let shift = if directions.next().unwrap() == Direction::Left { -1 } else { 1 };
+                if clear(&filled, &rock, (row, col + shift)) {
+                    col += shift;
+                }
+                if !clear(&filled, &rock, (row - 1, col)) {
+                    break;
+                }
+                row -= 1;
+            }
+            filled.extend(rock.filled.iter().map(|&(r, c)| (row + r as isize, col + c as isize)));
+            heights.push(top.max((row + rock.row_max as isize + 1) as usize));
+        }
+        heights
+    }
+
+    #[test]
+    fn wide_caves_with_custom_rocks() {
+        let art = "##########\n\n.#.\n###\n.#.\n\n#.........#\n###########\n\n#\n#";
+        let directions = get_directions(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>");
+        let spawn = Spawn { left: 5, above: 2 };
+
+        let mut cave = Cave::with_rocks(directions.clone(), 20, Rocks::parse(art).unwrap(), spawn).unwrap();
+        cave.run(2000);
 
-        // println!("{:#?}", cave.height_deltas.len());
+        assert_eq!(cave.heights, naive_heights(Rocks::parse(art).unwrap(), directions.clone(), 20, spawn, 2000));
+        assert_eq!(naive_heights(Rocks::new(), directions, 7, Spawn::default(), 2022)[2022], 3068);
+    }
+
+    #[test]
+    fn spawn_rule_is_configurable() {
+        // Single cells dropped in the corner get pushed one column over and stack up.
+        let rocks = Rocks::parse("#").unwrap();
+        let mut cave = Cave::with_rocks(get_directions(">"), 3, rocks, Spawn { left: 0, above: 0 }).unwrap();
+        cave.run(10);
+
+        assert_eq!(cave.height(), 10);
+        assert!((0..10).all(|row| cave.is_occupied((row, 1)) && !cave.is_occupied((row, 0))));
+    }
 
-        // for (&item, values) in cave.height_deltas.iter() {
-        //     println!("{:?}: {:?}", item, values);
+    #[test]
+    fn rocks_must_fit_the_cave() {
+        let rocks = || Rocks::parse("#\n\n####").unwrap();
+        let cave = Cave::with_rocks(get_directions("<"), 3, rocks(), Spawn { left: 0, above: 3 });
+        assert_eq!(cave.unwrap_err(), ShapeError::DoesNotFit { shape: 1, width: 3 });
+        let cave = Cave::with_rocks(get_directions("<"), 5, rocks(), Spawn { left: 2, above: 3 });
+        assert_eq!(cave.unwrap_err(), ShapeError::DoesNotFit { shape: 1, width: 5 });
+        assert!(Cave::with_rocks(get_directions("<"), 6, rocks(), Spawn { left: 2, above: 3 }).is_ok());
+        assert_eq!(Cave::new(get_directions("<"), 5).unwrap_err(), ShapeError::DoesNotFit { shape: 0, width: 5 });
+    }
 
-        // }
+    #[test]
+    fn rocks_are_bitmasks() {
+        assert_eq!(Rock::new(RockKind::Minus).masks, vec![0b1111]);
+        assert_eq!(Rock::new(RockKind::Plus).masks, vec![0b010, 0b111, 0b010]);
+        assert_eq!(Rock::new(RockKind::MirrorL).masks, vec![0b111, 0b100, 0b100]);
+        assert_eq!(Rock::new(RockKind::Vertical).masks, vec![0b1, 0b1, 0b1, 0b1]);
+        assert_eq!(Rock::new(RockKind::Square).masks, vec![0b11, 0b11]);
     }
-}
\ No newline at end of file
+}
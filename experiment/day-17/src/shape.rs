@@ -0,0 +1,188 @@
+use std::fmt::Display;
+
+use crate::{Rock, RockKind, Rocks};
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapeError {
+    /// A shape without a single `#` in it.
+    Empty,
+    /// Shapes are drawn with `#` for rock, and `.` or a space for air.
+    UnexpectedCharacter { line: usize, character: char },
+    /// Every row of a rock has to fit in a 64 bit mask.
+    TooWide { width: usize },
+    /// Rows are counted in a `u8`, so a rock can be at most 256 tall.
+    TooTall { height: usize },
+    /// A piece set with no pieces in it.
+    NoShapes,
+    /// The rock wouldn't fit between the walls where it spawns.
+    DoesNotFit { shape: usize, width: usize },
+}
+
+impl Display for ShapeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShapeError::Empty => write!(f, "A rock needs at least one `#`."),
+            ShapeError::UnexpectedCharacter { line, character } => {
+                write!(f, "Unexpected {:?} on line {} of a rock, only `#`, `.` and spaces are allowed.", character, line + 1)
+            },
+            ShapeError::TooWide { width } => write!(f, "A rock can be at most 64 wide, not {}.", width),
+            ShapeError::TooTall { height } => write!(f, "A rock can be at most 256 tall, not {}.", height),
+            ShapeError::NoShapes => write!(f, "There has to be at least one rock."),
+            ShapeError::DoesNotFit { shape, width } => write!(f, "Rock {} doesn't fit in a cave {} wide.", shape, width),
+        }
+    }
+}
+
+impl std::error::Error for ShapeError {}
+
+
+impl RockKind {
+    /// In the order they fall.
+    pub const ALL: [RockKind; 5] = [
+        RockKind::Minus,
+        RockKind::Plus,
+        RockKind::MirrorL,
+        RockKind::Vertical,
+        RockKind::Square,
+    ];
+
+    /// The rock as the puzzle draws it.
+    pub fn art(&self) -> &'static str {
+        match self {
+            RockKind::Minus => "####",
+            RockKind::Plus => ".#.\n###\n.#.",
+            RockKind::MirrorL => "..#\n..#\n###",
+            RockKind::Vertical => "#\n#\n#\n#",
+            RockKind::Square => "##\n##",
+        }
+    }
+}
+
+
+impl Rock {
+    /// Read a rock drawn with `#` where it's solid and `.` or a space where it isn't, top row first.
+    /// Whatever empty rows and columns surround it are trimmed off,
+    /// so that `(0, 0)` is at its bottom left.
+    pub fn from_art(art: &str) -> Result<Self, ShapeError> {
+        let lines = art.trim_matches('\n').lines().collect::<Vec<_>>();
+
+        let mut cells = vec![];
+        for (line, text) in lines.iter().enumerate() {
+            for (col, character) in text.trim_end().chars().enumerate() {
+                match character {
+                    '#' => cells.push((lines.len() - 1 - line, col)),
+                    '.' | ' ' => {},
+                    character => return Err(ShapeError::UnexpectedCharacter { line, character }),
+                }
+            }
+        }
+
+        let row_min = cells.iter().map(|&(row, _)| row).min().ok_or(ShapeError::Empty)?;
+        let col_min = cells.iter().map(|&(_, col)| col).min().ok_or(ShapeError::Empty)?;
+        let width = cells.iter().map(|&(_, col)| col - col_min + 1).max().unwrap_or(0);
+        if width > u64::BITS as usize {
+            return Err(ShapeError::TooWide { width });
+        }
+        let height = cells.iter().map(|&(row, _)| row - row_min + 1).max().unwrap_or(0);
+        if height > u8::MAX as usize + 1 {
+            return Err(ShapeError::TooTall { height });
+        }
+        let filled = cells
+            .into_iter()
+            .map(|(row, col)| ((row - row_min) as u8, (col - col_min) as u8))
+            .collect::<Vec<_>>();
+        Self::from_filled(filled)
+    }
+
+    /// Work out everything else about a rock from the cells it fills,
+    /// measured from its bottom left.
+    pub fn from_filled(mut filled: Vec<(u8, u8)>) -> Result<Self, ShapeError> {
+        filled.sort();
+        filled.dedup();
+        let row_max = filled.iter().map(|&(row, _)| row).max().ok_or(ShapeError::Empty)?;
+        let col_max = filled.iter().map(|&(_, col)| col).max().ok_or(ShapeError::Empty)?;
+        if col_max as u32 >= u64::BITS {
+            return Err(ShapeError::TooWide { width: col_max as usize + 1 });
+        }
+
+        let mut masks = vec![0_u64; row_max as usize + 1];
+        for &(row, col) in &filled {
+            masks[row as usize] |= 1 << col;
+        }
+
+        Ok(Self {
+            col_max,
+            row_max,
+            filled,
+            masks,
+        })
+    }
+}
+
+impl Rocks {
+    /// A whole piece set drawn one after the other, with a blank line between rocks.
+    pub fn parse(art: &str) -> Result<Self, ShapeError> {
+        let shapes = art
+            .split("\n\n")
+            .filter(|shape| !shape.trim().is_empty())
+            .map(Rock::from_art)
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_shapes(shapes)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trims_around_the_art() {
+        let rock = Rock::from_art("\n....\n.#..\n.##.\n....\n").unwrap();
+        assert_eq!(rock.filled, vec![(0, 0), (0, 1), (1, 0)]);
+        assert_eq!(rock.masks, vec![0b11, 0b01]);
+        assert_eq!((rock.row_max, rock.col_max), (1, 1));
+    }
+
+    #[test]
+    fn spaces_are_air() {
+        assert_eq!(Rock::from_art(" # \n###\n # "), Ok(Rock::new(RockKind::Plus)));
+        assert_eq!(Rock::from_art("  #\n. #\n###"), Ok(Rock::new(RockKind::MirrorL)));
+    }
+
+    #[test]
+    fn rejects_bad_art() {
+        assert_eq!(Rock::from_art("...\n..."), Err(ShapeError::Empty));
+        assert_eq!(Rock::from_art("#.\n#x"), Err(ShapeError::UnexpectedCharacter { line: 1, character: 'x' }));
+        assert_eq!(Rock::from_art(&"#".repeat(65)), Err(ShapeError::TooWide { width: 65 }));
+        assert_eq!(Rock::from_art(&format!("#{}#", ".".repeat(300))), Err(ShapeError::TooWide { width: 302 }));
+        assert_eq!(Rock::from_art(&"#\n".repeat(257)), Err(ShapeError::TooTall { height: 257 }));
+        assert_eq!(Rock::from_art(&format!("#\n{}#", ".\n".repeat(300))), Err(ShapeError::TooTall { height: 302 }));
+        assert_eq!(Rock::from_art(&"#\n".repeat(256)).unwrap().row_max, 255);
+        assert_eq!(Rocks::parse("\n\n"), Err(ShapeError::NoShapes));
+    }
+
+    #[test]
+    fn parses_the_puzzle_pieces() {
+        let art = "####
+
+.#.
+###
+.#.
+
+..#
+..#
+###
+
+#
+#
+#
+#
+
+##
+##
+";
+        assert_eq!(Rocks::parse(art).unwrap(), Rocks::new());
+    }
+}
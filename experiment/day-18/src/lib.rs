@@ -0,0 +1,183 @@
+use advent::Solution;
+use std::collections::{HashSet, VecDeque};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Cube((isize, isize, isize));
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    Front,
+    Back
+}
+
+
+impl Cube {
+    pub fn shares_side(&self, other: &Cube) -> Option<Side> {
+        let (x1, y1, z1) = self.0;
+        let (x2, y2, z2) = other.0;
+
+        if y1 == y2 && z1 == z2 {
+            if x1 == x2 - 1 {
+                return Some(Side::Right);
+            } else if x1 == x2 + 1 {
+                return Some(Side::Left);
+            }
+        } else if x1 == x2 && z1 == z2 {
+            if y1 == y2 - 1 {
+                return Some(Side::Bottom);
+            } else if y1 == y2 + 1 {
+                return Some(Side::Top);
+            }
+        } else if x1 == x2 && y1 == y2 {
+            if z1 == z2 - 1 {
+                return Some(Side::Back);
+            } else if z1 == z2 + 1 {
+                return Some(Side::Front);
+            }
+        }
+        None
+    }
+
+    pub fn neighbors(&self) -> Vec<Cube> {
+        let mut neighbors = Vec::new();
+        let (x, y, z) = self.0;
+        neighbors.push(Cube((x + 1, y, z)));
+        neighbors.push(Cube((x - 1, y, z)));
+        neighbors.push(Cube((x, y + 1, z)));
+        neighbors.push(Cube((x, y - 1, z)));
+        neighbors.push(Cube((x, y, z + 1)));
+        neighbors.push(Cube((x, y, z - 1)));
+        neighbors
+    }
+}
+
+pub fn solve_part1(cubes: &[Cube]) -> usize {
+    let lava = cubes.iter().copied().collect::<HashSet<_>>();
+
+    lava
+        .iter()
+        .flat_map(|cube| cube.neighbors())
+        .filter(|neighbor| !lava.contains(neighbor))
+        .count()
+}
+
+/// Only count the sides that steam can reach from outside the droplet,
+/// by flooding the air in a box around it that's one bigger on every side.
+pub fn solve_part2(cubes: &[Cube]) -> usize {
+    let lava = cubes.iter().copied().collect::<HashSet<_>>();
+    let Some(&first) = cubes.first() else {
+        return 0;
+    };
+
+    let (mut min, mut max) = (first.0, first.0);
+    for &Cube((x, y, z)) in cubes {
+        min = (min.0.min(x), min.1.min(y), min.2.min(z));
+        max = (max.0.max(x), max.1.max(y), max.2.max(z));
+    }
+    let min = (min.0 - 1, min.1 - 1, min.2 - 1);
+    let max = (max.0 + 1, max.1 + 1, max.2 + 1);
+    let in_bounds = |&Cube((x, y, z)): &Cube| {
+        (min.0..=max.0).contains(&x) && (min.1..=max.1).contains(&y) && (min.2..=max.2).contains(&z)
+    };
+
+    let mut exterior = 0;
+    let mut seen = HashSet::from([Cube(min)]);
+    let mut queue = VecDeque::from([Cube(min)]);
+
+    while let Some(air) = queue.pop_front() {
+        for neighbor in air.neighbors() {
+            if lava.contains(&neighbor) {
+                exterior += 1;
+            } else if in_bounds(&neighbor) && seen.insert(neighbor) {
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    exterior
+}
+
+pub fn parse_cubes(s: &str) -> Vec<Cube> {
+    let mut cubes = Vec::new();
+    s
+    .lines()
+    .for_each(|line| {
+        let cube_str = line.split(",").collect::<Vec<_>>();
+        let x = cube_str[0].parse::<isize>().unwrap();
+        let y = cube_str[1].parse::<isize>().unwrap();
+        let z = cube_str[2].parse::<isize>().unwrap();
+        cubes.push(Cube((x, y, z)));
+    });
+
+    cubes
+}
+
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<Cube>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Self::Input {
+        parse_cubes(input)
+    }
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        solve_part1(input)
+    }
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        solve_part2(input)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5";
+
+    #[test]
+    fn smol() {
+        let cubes = parse_cubes(EXAMPLE);
+        assert_eq!(solve_part1(&cubes), 64);
+        assert_eq!(solve_part2(&cubes), 58);
+    }
+
+    #[test]
+    fn two_cubes() {
+        let cubes = parse_cubes("1,1,1\n2,1,1");
+        assert_eq!(solve_part1(&cubes), 10);
+        assert_eq!(solve_part2(&cubes), 10);
+    }
+
+    #[test]
+    fn hollow_shell_hides_its_inside() {
+        // A 3x3x3 block with the middle missing.
+        let cubes = (0..27)
+            .map(|i| Cube((i % 3, i / 3 % 3, i / 9)))
+            .filter(|&cube| cube != Cube((1, 1, 1)))
+            .collect::<Vec<_>>();
+        assert_eq!(solve_part1(&cubes), 54 + 6);
+        assert_eq!(solve_part2(&cubes), 54);
+    }
+}
@@ -0,0 +1,203 @@
+mod parse;
+pub use parse::*;
+use advent::Solution;
+use rayon::prelude::*;
+
+
+pub const ORE: usize = 0;
+pub const CLAY: usize = 1;
+pub const OBSIDIAN: usize = 2;
+pub const GEODE: usize = 3;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Blueprint {
+    pub id: u32,
+    /// How much ore, clay and obsidian each kind of robot costs,
+    /// indexed by the kind of robot (`ORE`, `CLAY`, `OBSIDIAN`, `GEODE`).
+    pub costs: [[u32; 3]; 4],
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct State {
+    pub time_left: u32,
+    pub resources: [u32; 4],
+    pub robots: [u32; 4],
+}
+
+impl State {
+    pub fn new(time_left: u32) -> Self {
+        Self {
+            time_left,
+            resources: [0; 4],
+            robots: [1, 0, 0, 0],
+        }
+    }
+
+    /// The geodes we end up with if we stop building robots now.
+    pub fn idle_geodes(&self) -> u32 {
+        self.resources[GEODE] + self.robots[GEODE] * self.time_left
+    }
+
+    /// The geodes we'd end up with if we could build a geode robot every remaining minute.
+    /// No branch from here can do any better.
+    pub fn upper_bound(&self) -> u32 {
+        let t = self.time_left;
+        self.idle_geodes() + t * t.saturating_sub(1) / 2
+    }
+
+    /// Wait until the robot is affordable, then build it.
+    /// `None` if it can't be built with the robots we have, or not in time to be of any use.
+    pub fn build(&self, blueprint: &Blueprint, robot: usize) -> Option<State> {
+        let mut wait = 0;
+        for (resource, &cost) in blueprint.costs[robot].iter().enumerate() {
+            if cost > self.resources[resource] {
+                if self.robots[resource] == 0 {
+                    return None;
+                }
+                let missing = cost - self.resources[resource];
+                wait = wait.max(missing.div_ceil(self.robots[resource]));
+            }
+        }
+
+        // It takes a minute to build, and it has to have a minute left to collect anything.
+        if wait + 1 >= self.time_left {
+            return None;
+        }
+
+        let mut next = *self;
+        next.time_left -= wait + 1;
+        for resource in 0..4 {
+            next.resources[resource] += self.robots[resource] * (wait + 1);
+        }
+        for (resource, &cost) in blueprint.costs[robot].iter().enumerate() {
+            next.resources[resource] -= cost;
+        }
+        next.robots[robot] += 1;
+        Some(next)
+    }
+}
+
+
+impl Blueprint {
+    /// We can only spend so much of a resource per minute,
+    /// so there's no point in having more robots collect it than that.
+    pub fn max_useful_robots(&self) -> [u32; 4] {
+        let mut max = [0, 0, 0, u32::MAX];
+        for costs in self.costs.iter() {
+            for resource in 0..3 {
+                max[resource] = max[resource].max(costs[resource]);
+            }
+        }
+        max
+    }
+
+    fn search(&self, state: State, max_robots: &[u32; 4], best: &mut u32) {
+        *best = (*best).max(state.idle_geodes());
+        if state.upper_bound() <= *best {
+            return;
+        }
+
+        // Geode robots first so that we find good answers early and prune more.
+        for robot in (ORE..=GEODE).rev() {
+            if state.robots[robot] >= max_robots[robot] {
+                continue;
+            }
+            if let Some(next) = state.build(self, robot) {
+                self.search(next, max_robots, best);
+            }
+        }
+    }
+
+    /// The most geodes that can be opened in the given number of minutes.
+    pub fn max_geodes(&self, minutes: u32) -> u32 {
+        let mut best = 0;
+        self.search(State::new(minutes), &self.max_useful_robots(), &mut best);
+        best
+    }
+
+    pub fn quality_level(&self, minutes: u32) -> u32 {
+        self.id * self.max_geodes(minutes)
+    }
+}
+
+
+pub fn get_blueprints(s: &str) -> Vec<Blueprint> {
+    parse_blueprints(s).unwrap().1
+}
+
+pub fn solve_part1(blueprints: &[Blueprint]) -> u32 {
+    blueprints
+        .par_iter()
+        .map(|blueprint| blueprint.quality_level(24))
+        .sum()
+}
+
+pub fn solve_part2(blueprints: &[Blueprint]) -> u32 {
+    blueprints
+        .par_iter()
+        .take(3)
+        .map(|blueprint| blueprint.max_geodes(32))
+        .product()
+}
+
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Vec<Blueprint>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Self::Input {
+        get_blueprints(input)
+    }
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        solve_part1(input)
+    }
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        solve_part2(input)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const EXAMPLE: &str = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+
+    #[test_case(0, 24, 9)]
+    #[test_case(1, 24, 12)]
+    #[test_case(0, 32, 56)]
+    #[test_case(1, 32, 62)]
+    fn max_geodes(idx: usize, minutes: u32, expected: u32) {
+        let blueprints = get_blueprints(EXAMPLE);
+        assert_eq!(blueprints[idx].max_geodes(minutes), expected);
+    }
+
+    #[test]
+    fn smol() {
+        let blueprints = get_blueprints(EXAMPLE);
+        assert_eq!(solve_part1(&blueprints), 33);
+        assert_eq!(solve_part2(&blueprints), 56 * 62);
+    }
+
+    #[test]
+    fn build_waits_for_resources() {
+        let blueprints = get_blueprints(EXAMPLE);
+        let state = State::new(24);
+
+        // Nothing collects clay yet.
+        assert_eq!(state.build(&blueprints[0], OBSIDIAN), None);
+
+        // 2 ore for a clay robot takes two minutes to collect, plus one to build.
+        let next = state.build(&blueprints[0], CLAY).unwrap();
+        assert_eq!(next.time_left, 21);
+        assert_eq!(next.resources, [1, 0, 0, 0]);
+        assert_eq!(next.robots, [1, 1, 0, 0]);
+    }
+}
@@ -0,0 +1,11 @@
+use advent::{InputError, InputLoader};
+use day_19::*;
+
+fn main() -> Result<(), InputError> {
+    let input = InputLoader::from_args(19).load()?;
+    let blueprints = get_blueprints(&input);
+    println!("Part 1: {}", solve_part1(&blueprints));
+    println!("Part 2: {}", solve_part2(&blueprints));
+
+    Ok(())
+}
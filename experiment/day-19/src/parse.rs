@@ -0,0 +1,88 @@
+use crate::*;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{multispace0, multispace1, u32},
+    combinator::map,
+    multi::many1,
+    sequence::{delimited, preceded, tuple},
+    IResult,
+};
+
+/// "Each obsidian robot costs 3 ore and 14 clay."
+fn parse_cost<'a>(s: &'a str, robot: &str, resources: &[&str]) -> IResult<&'a str, Vec<u32>> {
+    let (mut s, _) = tuple((tag("Each "), tag(robot), tag(" robot costs ")))(s)?;
+
+    let mut amounts = vec![];
+    for (idx, resource) in resources.iter().enumerate() {
+        if idx > 0 {
+            (s, _) = tag(" and ")(s)?;
+        }
+        let (rem, amount) = u32(s)?;
+        (s, _) = preceded(tag(" "), tag(*resource))(rem)?;
+        amounts.push(amount);
+    }
+    let (s, _) = tag(".")(s)?;
+    Ok((s, amounts))
+}
+
+pub fn parse_blueprint(s: &str) -> IResult<&str, Blueprint> {
+    map(
+        tuple((
+            delimited(tag("Blueprint "), u32, tag(":")),
+            preceded(multispace1, |s| parse_cost(s, "ore", &["ore"])),
+            preceded(multispace1, |s| parse_cost(s, "clay", &["ore"])),
+            preceded(multispace1, |s| parse_cost(s, "obsidian", &["ore", "clay"])),
+            preceded(multispace1, |s| parse_cost(s, "geode", &["ore", "obsidian"])),
+        )),
+        |(id, ore, clay, obsidian, geode)| Blueprint {
+            id,
+            costs: [
+                [ore[0], 0, 0],
+                [clay[0], 0, 0],
+                [obsidian[0], obsidian[1], 0],
+                [geode[0], 0, geode[1]],
+            ],
+        },
+    )(s)
+}
+
+/// Blueprints come one per line in the input,
+/// but wrapped over several lines in the puzzle text.
+pub fn parse_blueprints(s: &str) -> IResult<&str, Vec<Blueprint>> {
+    many1(delimited(multispace0, parse_blueprint, multispace0))(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_blueprint() {
+        let s = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.";
+        let (rem, blueprint) = parse_blueprint(s).unwrap();
+        assert_eq!(rem, "");
+        assert_eq!(blueprint.id, 1);
+        assert_eq!(blueprint.costs, [[4, 0, 0], [2, 0, 0], [3, 14, 0], [2, 0, 7]]);
+    }
+
+    #[test]
+    fn test_parse_wrapped_blueprints() {
+        let s = "
+Blueprint 1:
+  Each ore robot costs 4 ore.
+  Each clay robot costs 2 ore.
+  Each obsidian robot costs 3 ore and 14 clay.
+  Each geode robot costs 2 ore and 7 obsidian.
+
+Blueprint 2:
+  Each ore robot costs 2 ore.
+  Each clay robot costs 3 ore.
+  Each obsidian robot costs 3 ore and 8 clay.
+  Each geode robot costs 3 ore and 12 obsidian.
+";
+        let (rem, blueprints) = parse_blueprints(s).unwrap();
+        assert_eq!(rem, "");
+        assert_eq!(blueprints.len(), 2);
+        assert_eq!(blueprints[1].costs, [[2, 0, 0], [3, 0, 0], [3, 8, 0], [3, 0, 12]]);
+    }
+}
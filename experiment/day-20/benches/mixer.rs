@@ -0,0 +1,39 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use day_20::*;
+
+
+/// The real input, repeated until there are `len` numbers, to see how each backend scales.
+fn numbers(len: usize) -> Mixer {
+    let input = get_input(include_str!("../src/input.txt"), 811_589_153);
+    input
+        .iter()
+        .cycle()
+        .take(len)
+        .enumerate()
+        .map(|(idx, number)| Number(number.0, idx))
+        .collect()
+}
+
+fn mixing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mix once");
+    group.sample_size(10);
+
+    for len in [5_000, 20_000] {
+        let numbers = numbers(len);
+
+        group.bench_with_input(BenchmarkId::new("vec", len), &numbers, |b, numbers| {
+            b.iter_batched(|| numbers.clone(), |mut mixer| mixer.run(1), BatchSize::LargeInput)
+        });
+        group.bench_with_input(BenchmarkId::new("treap", len), &numbers, |b, numbers| {
+            b.iter_batched(
+                || numbers.iter().copied().collect::<TreapMixer>(),
+                |mut mixer| mixer.run(1),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, mixing);
+criterion_main!(benches);
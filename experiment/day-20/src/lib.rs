@@ -1,5 +1,9 @@
-use std::{collections::HashMap, fmt::{Debug, Display}, cmp::Ordering};
-use indicatif::{ProgressBar, ProgressIterator};
+mod treap;
+pub use treap::*;
+
+use advent::Solution;
+use std::fmt::Debug;
+use indicatif::ProgressIterator;
 
 
 pub type Value = i128;
@@ -57,7 +61,7 @@ impl AOCDay20 for Mixer {
     }
 }
 
-pub fn solve(mixer: &mut Mixer, times: usize) -> Value {
+pub fn solve<M: AOCDay20>(mixer: &mut M, times: usize) -> Value {
     mixer.run(times);
     let v1 = mixer.get_after(0, 1000).unwrap();
     let v2 = mixer.get_after(0, 2000).unwrap();
@@ -66,6 +70,41 @@ pub fn solve(mixer: &mut Mixer, times: usize) -> Value {
 }
 
 
+pub fn get_input(s: &str, scale_factor: Value) -> Mixer {
+    s
+    .lines()
+    .map_while(
+        |x| x.parse::<Value>().ok()
+    )
+    .enumerate()
+    .map(|(idx, x)| Number(x * scale_factor, idx))
+    .collect::<Vec<_>>()
+}
+
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Mixer;
+    type Answer1 = Value;
+    type Answer2 = Value;
+
+    fn parse(&self, input: &str) -> Self::Input {
+        get_input(input, 1)
+    }
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        solve(&mut input.iter().copied().collect::<TreapMixer>(), 1)
+    }
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        let mut mixer = input
+            .iter()
+            .map(|number| Number(number.0 * 811_589_153, number.1))
+            .collect::<TreapMixer>();
+        solve(&mut mixer, 10)
+    }
+}
+
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,9 +122,13 @@ mod tests {
         ];
 
         let mut mixer2 = mixer.clone().into_iter().map(|number| Number(number.0 * 811589153, number.1)).collect::<Vec<_>>();
+        let mut treap = mixer.iter().copied().collect::<TreapMixer>();
+        let mut treap2 = mixer2.iter().copied().collect::<TreapMixer>();
         
         assert_eq!(3, solve(&mut mixer, 1));
         assert_eq!(1623178306, solve(&mut mixer2, 10));
-        
+
+        assert_eq!(3, solve(&mut treap, 1));
+        assert_eq!(1623178306, solve(&mut treap2, 10));
     }
-}
\ No newline at end of file
+}
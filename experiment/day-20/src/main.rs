@@ -1,24 +1,14 @@
-pub use day_20::*;
+use advent::{InputError, InputLoader};
+use day_20::*;
 
-pub const INPUT: &str = include_str!("input.txt");
+fn main() -> Result<(), InputError> {
+    let input = InputLoader::from_args(20).load()?;
 
-pub fn get_input(s: &str, scale_factor: Value) -> Mixer {
-    s
-    .lines()
-    .map_while(
-        |x| x.parse::<Value>().ok()
-    )
-    .enumerate()
-    .map(|(idx, x)| Number(x * scale_factor, idx))
-    .collect::<Vec<_>>()
-}
-
-
-pub fn main() {
-    
-    let mut mixer = get_input(INPUT, 1);
+    let mut mixer = get_input(&input, 1).into_iter().collect::<TreapMixer>();
     println!("Solution Part 1: {}", solve(&mut mixer, 1));
 
-    let mut mixer = get_input(INPUT, 811_589_153);
+    let mut mixer = get_input(&input, 811_589_153).into_iter().collect::<TreapMixer>();
     println!("Solution Part 2: {}", solve(&mut mixer, 10));
+
+    Ok(())
 }
@@ -0,0 +1,227 @@
+use crate::{AOCDay20, Number, Value};
+
+
+#[derive(Debug, Clone)]
+struct Node {
+    number: Number,
+    priority: u64,
+    /// Number of nodes in the subtree rooted here.
+    size: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+    parent: Option<usize>,
+}
+
+
+/// The same circular list as [`Mixer`](crate::Mixer), kept in an implicit treap
+/// so that finding a number's position and moving it are both O(log n).
+///
+/// The nodes live in a `Vec` in their original order, so the number that started
+/// out at position `i` is always `nodes[i]`, and its current position is found by
+/// walking up to the root.
+#[derive(Debug, Clone)]
+pub struct TreapMixer {
+    nodes: Vec<Node>,
+    root: Option<usize>,
+}
+
+impl FromIterator<Number> for TreapMixer {
+    fn from_iter<T: IntoIterator<Item = Number>>(iter: T) -> Self {
+        // Any fixed xorshift sequence makes for a balanced enough tree.
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut mixer = TreapMixer { nodes: vec![], root: None };
+
+        for number in iter {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+
+            let id = mixer.nodes.len();
+            mixer.nodes.push(Node { number, priority: seed, size: 1, left: None, right: None, parent: None });
+            mixer.root = mixer.merge(mixer.root, Some(id));
+        }
+        mixer.detach(mixer.root);
+        mixer
+    }
+}
+
+impl TreapMixer {
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The numbers in their current order.
+    pub fn to_vec(&self) -> Vec<Number> {
+        let mut numbers = Vec::with_capacity(self.len());
+        let mut stack = vec![];
+        let mut current = self.root;
+        while current.is_some() || !stack.is_empty() {
+            while let Some(id) = current {
+                stack.push(id);
+                current = self.nodes[id].left;
+            }
+            let id = stack.pop().unwrap();
+            numbers.push(self.nodes[id].number);
+            current = self.nodes[id].right;
+        }
+        numbers
+    }
+
+    fn size(&self, node: Option<usize>) -> usize {
+        node.map_or(0, |id| self.nodes[id].size)
+    }
+
+    fn detach(&mut self, node: Option<usize>) {
+        if let Some(id) = node {
+            self.nodes[id].parent = None;
+        }
+    }
+
+    /// Fix up the size of `id` and point its children back at it.
+    fn update(&mut self, id: usize) {
+        let (left, right) = (self.nodes[id].left, self.nodes[id].right);
+        self.nodes[id].size = 1 + self.size(left) + self.size(right);
+        for child in [left, right].into_iter().flatten() {
+            self.nodes[child].parent = Some(id);
+        }
+    }
+
+    /// Split off the first `count` nodes of `node` from the rest.
+    fn split(&mut self, node: Option<usize>, count: usize) -> (Option<usize>, Option<usize>) {
+        let Some(id) = node else {
+            return (None, None);
+        };
+
+        let left_size = self.size(self.nodes[id].left);
+        if count <= left_size {
+            let (first, rest) = self.split(self.nodes[id].left, count);
+            self.nodes[id].left = rest;
+            self.update(id);
+            (first, Some(id))
+        } else {
+            let (first, rest) = self.split(self.nodes[id].right, count - left_size - 1);
+            self.nodes[id].right = first;
+            self.update(id);
+            (Some(id), rest)
+        }
+    }
+
+    /// Everything in `first` followed by everything in `second`.
+    fn merge(&mut self, first: Option<usize>, second: Option<usize>) -> Option<usize> {
+        match (first, second) {
+            (None, node) | (node, None) => node,
+            (Some(a), Some(b)) if self.nodes[a].priority > self.nodes[b].priority => {
+                self.nodes[a].right = self.merge(self.nodes[a].right, second);
+                self.update(a);
+                Some(a)
+            },
+            (Some(a), Some(b)) => {
+                self.nodes[b].left = self.merge(Some(a), self.nodes[b].left);
+                self.update(b);
+                Some(b)
+            },
+        }
+    }
+
+    /// Where the node is in the list right now.
+    fn position(&self, id: usize) -> usize {
+        let mut position = self.size(self.nodes[id].left);
+        let mut current = id;
+        while let Some(parent) = self.nodes[current].parent {
+            if self.nodes[parent].right == Some(current) {
+                position += self.size(self.nodes[parent].left) + 1;
+            }
+            current = parent;
+        }
+        position
+    }
+
+    /// The node at the given position in the list.
+    fn at(&self, mut position: usize) -> Option<usize> {
+        let mut current = self.root;
+        while let Some(id) = current {
+            let left_size = self.size(self.nodes[id].left);
+            match position.cmp(&left_size) {
+                std::cmp::Ordering::Less => current = self.nodes[id].left,
+                std::cmp::Ordering::Equal => return Some(id),
+                std::cmp::Ordering::Greater => {
+                    position -= left_size + 1;
+                    current = self.nodes[id].right;
+                },
+            }
+        }
+        None
+    }
+}
+
+
+impl AOCDay20 for TreapMixer {
+
+    fn step(&mut self, current_position: usize) {
+        if self.len() < 2 {
+            return;
+        }
+        let modulus = (self.len() as Value) - 1;
+        let index = self.position(current_position);
+        let value = self.nodes[current_position].number.0;
+        let new_index = (((index as Value + value) % modulus) + modulus) % modulus;
+
+        let (before, rest) = self.split(self.root, index);
+        let (moved, after) = self.split(rest, 1);
+        let without = self.merge(before, after);
+
+        let (before, after) = self.split(without, new_index as usize);
+        let before = self.merge(before, moved);
+        self.root = self.merge(before, after);
+        self.detach(self.root);
+    }
+
+    fn get_after(&self, item: Value, count: usize) -> Option<Value> {
+        let id = self.nodes.iter().position(|node| node.number.0 == item)?;
+        let index = (self.position(id) + count) % self.len();
+        self.at(index).map(|id| self.nodes[id].number.0)
+    }
+
+    fn run(&mut self, times: usize) {
+        for _ in 0..times {
+            for current_position in 0..self.len() {
+                self.step(current_position);
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_input, Mixer};
+
+    #[test]
+    fn moves_like_the_vec() {
+        let numbers = get_input(include_str!("input.txt"), 811_589_153);
+        let mut mixer: Mixer = numbers.clone();
+        let mut treap = numbers.into_iter().collect::<TreapMixer>();
+
+        for current_position in 0..mixer.len() {
+            mixer.step(current_position);
+            treap.step(current_position);
+        }
+
+        let order = |numbers: Vec<Number>| numbers.iter().map(|number| number.1).collect::<Vec<_>>();
+        assert_eq!(order(treap.to_vec()), order(mixer));
+    }
+
+    #[test]
+    fn keeps_order_when_built() {
+        let numbers = (0..100).map(|idx| Number(idx as Value, idx)).collect::<Vec<_>>();
+        let treap = numbers.iter().copied().collect::<TreapMixer>();
+        assert_eq!(treap.len(), 100);
+        assert_eq!(treap.get_after(10, 95), Some(5));
+        assert_eq!(treap.get_after(100, 1), None);
+    }
+}
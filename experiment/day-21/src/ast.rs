@@ -0,0 +1,272 @@
+use std::fmt::Display;
+
+use crate::{Expression, Operator, Rational, SolveError, Tree, HUMN, ROOT};
+
+
+/// A monkey's job with every name it waits on filled in,
+/// down to the numbers and `humn`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ast {
+    Literal(Rational),
+    Humn,
+    Operation {
+        operator: Operator,
+        left: Box<Ast>,
+        right: Box<Ast>,
+    },
+}
+
+impl Ast {
+    pub fn operation(operator: Operator, left: Ast, right: Ast) -> Self {
+        Ast::Operation { operator, left: Box::new(left), right: Box::new(right) }
+    }
+
+    pub fn contains_humn(&self) -> bool {
+        match self {
+            Ast::Literal(_) => false,
+            Ast::Humn => true,
+            Ast::Operation { left, right, .. } => left.contains_humn() || right.contains_humn(),
+        }
+    }
+
+    /// Work out everything that doesn't depend on `humn`, and drop operations
+    /// that don't do anything (`+ 0`, `* 1`, `/ 1`, ...).
+    /// Divisions by zero, and anything that overflows, are left as they are.
+    pub fn fold(self) -> Ast {
+        let Ast::Operation { operator, left, right } = self else {
+            return self;
+        };
+        let (left, right) = (left.fold(), right.fold());
+
+        match (operator, &left, &right) {
+            (Operator::Divide, _, Ast::Literal(r)) if r.is_zero() => Ast::operation(operator, left, right),
+            (_, Ast::Literal(l), Ast::Literal(r)) => match operator.apply(*l, *r) {
+                Some(value) => Ast::Literal(value),
+                None => Ast::operation(operator, left, right),
+            },
+            (Operator::Add, Ast::Literal(l), _) if l.is_zero() => right,
+            (Operator::Add | Operator::Subtract, _, Ast::Literal(r)) if r.is_zero() => left,
+            (Operator::Multiply, Ast::Literal(l), _) if *l == Rational::from(1) => right,
+            (Operator::Multiply | Operator::Divide, _, Ast::Literal(r)) if *r == Rational::from(1) => left,
+            _ => Ast::operation(operator, left, right),
+        }
+    }
+}
+
+impl Display for Ast {
+    /// Infix, with only the parentheses that are needed.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Ast::Literal(value) if value.denominator() != 1 || value.numerator() < 0 => write!(f, "({})", value),
+            Ast::Literal(value) => write!(f, "{}", value),
+            Ast::Humn => write!(f, "{}", HUMN),
+            Ast::Operation { operator, left, right } => {
+                let binds_looser = |child: &Ast| match child {
+                    Ast::Operation { operator: inner, .. } => inner.precedence() < operator.precedence(),
+                    _ => false,
+                };
+                // a - (b - c) and a / (b / c) need them on the right as well.
+                let binds_as_tight = |child: &Ast| match child {
+                    Ast::Operation { operator: inner, .. } => inner.precedence() == operator.precedence(),
+                    _ => false,
+                };
+
+                if binds_looser(left) {
+                    write!(f, "({})", left)?;
+                } else {
+                    write!(f, "{}", left)?;
+                }
+                write!(f, " {} ", operator)?;
+                let non_associative = matches!(operator, Operator::Subtract | Operator::Divide);
+                if binds_looser(right) || (non_associative && binds_as_tight(right)) {
+                    write!(f, "({})", right)
+                } else {
+                    write!(f, "{}", right)
+                }
+            }
+        }
+    }
+}
+
+
+/// What `root` actually checks: that both of its sides are equal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Equation {
+    pub left: Ast,
+    pub right: Ast,
+}
+
+impl Equation {
+    pub fn fold(self) -> Self {
+        Self { left: self.left.fold(), right: self.right.fold() }
+    }
+}
+
+impl Display for Equation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {}", self.left, self.right)
+    }
+}
+
+
+impl Tree {
+    /// The expression behind `key`, with `humn` left as an unknown.
+    pub fn lower(&self, key: &str) -> Result<Ast, SolveError> {
+        self.validate(key)?;
+        Ok(self.lower_unchecked(key))
+    }
+
+    fn lower_unchecked(&self, key: &str) -> Ast {
+        if key == HUMN {
+            return Ast::Humn;
+        }
+        match &self.statements[key] {
+            Expression::Literal(value) => Ast::Literal(Rational::from(*value)),
+            Expression::Operation(op) => Ast::operation(
+                op.operator,
+                self.lower_unchecked(&op.left),
+                self.lower_unchecked(&op.right),
+            ),
+        }
+    }
+
+    /// The equation `root` checks for in part 2, folded down to
+    /// a chain of operations on `humn` on one side and a number on the other.
+    pub fn equation(&self) -> Result<Equation, SolveError> {
+        match self.statements.get(ROOT) {
+            None => Err(SolveError::Undefined(ROOT.to_string())),
+            Some(Expression::Literal(_)) => Err(SolveError::MissingHumn),
+            Some(Expression::Operation(op)) => {
+                self.validate(ROOT)?;
+                let equation = Equation {
+                    left: self.lower_unchecked(&op.left),
+                    right: self.lower_unchecked(&op.right),
+                };
+                Ok(equation.fold())
+            }
+        }
+    }
+
+    /// Who waits on whom, as a Graphviz digraph.
+    /// Edges point from a monkey to the monkeys it listens to.
+    pub fn to_dot(&self) -> String {
+        let mut names = self.statements.keys().collect::<Vec<_>>();
+        names.sort();
+
+        let mut dot = String::from("digraph monkeys {\n");
+        for name in names {
+            let (label, shape) = match &self.statements[name] {
+                Expression::Literal(value) => (format!("{}: {}", name, value), "box"),
+                Expression::Operation(op) => (format!("{}: {}", name, op.operator), "ellipse"),
+            };
+            let style = match name.as_str() {
+                ROOT | HUMN => ", style=bold",
+                _ => "",
+            };
+            dot.push_str(&format!("    {} [label=\"{}\", shape={}{}];\n", name, label, shape, style));
+
+            if let Expression::Operation(op) = &self.statements[name] {
+                dot.push_str(&format!("    {} -> {} [label=\"left\"];\n", name, op.left));
+                dot.push_str(&format!("    {} -> {} [label=\"right\"];\n", name, op.right));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::statements;
+
+    const EXAMPLE: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32";
+
+    fn tree(input: &str) -> Tree {
+        statements(input).unwrap().1.into()
+    }
+
+    #[test]
+    fn folds_everything_but_humn() {
+        let equation = tree(EXAMPLE).equation().unwrap();
+        assert_eq!(equation.to_string(), "(4 + 2 * (humn - 3)) / 4 = 150");
+        assert_eq!(equation.right, Ast::Literal(Rational::from(150)));
+        assert!(equation.left.contains_humn());
+    }
+
+    #[test]
+    fn prints_only_the_parentheses_it_needs() {
+        let literal = |value: i64| Ast::Literal(Rational::from(value));
+        let difference = Ast::operation(Operator::Subtract, Ast::Humn, literal(1));
+        let nested = Ast::operation(Operator::Subtract, literal(2), difference.clone());
+        assert_eq!(nested.to_string(), "2 - (humn - 1)");
+
+        let left_leaning = Ast::operation(Operator::Subtract, difference.clone(), literal(2));
+        assert_eq!(left_leaning.to_string(), "humn - 1 - 2");
+
+        let product = Ast::operation(Operator::Multiply, difference, literal(3));
+        assert_eq!(product.to_string(), "(humn - 1) * 3");
+
+        assert_eq!(Ast::Literal(Rational::new(-7, 2)).to_string(), "(-7/2)");
+    }
+
+    #[test]
+    fn drops_operations_that_do_nothing() {
+        let ast = tree("root: a * one
+a: humn + zero
+one: 1
+zero: 0
+humn: 5").lower(ROOT).unwrap();
+        assert_eq!(ast.to_string(), "(humn + 0) * 1");
+        assert_eq!(ast.fold(), Ast::Humn);
+    }
+
+    #[test]
+    fn exports_dot() {
+        let dot = tree("root: humn + one
+humn: 5
+one: 1").to_dot();
+        assert_eq!(dot, "digraph monkeys {
+    humn [label=\"humn: 5\", shape=box, style=bold];
+    one [label=\"one: 1\", shape=box];
+    root [label=\"root: +\", shape=ellipse, style=bold];
+    root -> humn [label=\"left\"];
+    root -> one [label=\"right\"];
+}
+");
+    }
+
+    #[test]
+    fn reports_cycles_and_undefined_names() {
+        let cycle = tree("root: a + humn
+a: b * two
+b: a - two
+two: 2
+humn: 5");
+        assert_eq!(cycle.lower(ROOT), Err(SolveError::Cycle { node: "a".to_string() }));
+        assert_eq!(cycle.equation(), Err(SolveError::Cycle { node: "a".to_string() }));
+        assert_eq!(cycle.solve_for_humn(), Err(SolveError::Cycle { node: "a".to_string() }));
+
+        let undefined = tree("root: a + humn
+a: b * two
+two: 2
+humn: 5");
+        assert_eq!(undefined.lower(ROOT), Err(SolveError::Undefined("b".to_string())));
+        assert_eq!(undefined.evaluate(ROOT, &mut Default::default()), Err(SolveError::Undefined("b".to_string())));
+    }
+}
@@ -1,4 +1,11 @@
+mod ast;
+mod rational;
+pub use ast::*;
+pub use rational::*;
+
+use advent::Solution;
 use std::collections::HashMap;
+use std::fmt::Display;
 use nom::IResult;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -9,6 +16,38 @@ pub enum Operator {
     Divide
 }
 
+impl Operator {
+    /// `None` if it overflows, or divides by zero.
+    pub fn apply(&self, left: Rational, right: Rational) -> Option<Rational> {
+        match self {
+            Operator::Add => left.checked_add(right),
+            Operator::Subtract => left.checked_sub(right),
+            Operator::Multiply => left.checked_mul(right),
+            Operator::Divide => left.checked_div(right),
+        }
+    }
+
+    /// How tightly it binds when written infix.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            Operator::Add | Operator::Subtract => 1,
+            Operator::Multiply | Operator::Divide => 2,
+        }
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            Operator::Add => '+',
+            Operator::Subtract => '-',
+            Operator::Multiply => '*',
+            Operator::Divide => '/',
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Operation {
@@ -19,7 +58,7 @@ pub struct Operation {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Expression {
-    Literal(f64),
+    Literal(i64),
     Operation(Operation)
 }
 
@@ -47,6 +86,12 @@ impl From<Vec<Statement>> for Tree {
 }
 
 
+impl Default for Tree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Tree {
     pub fn new() -> Self {
         Self {
@@ -58,122 +103,232 @@ impl Tree {
         self.statements.insert(statement.target.clone(), statement.value);
     }
 
-    pub fn evaluate_part1(&self, key: &str, memo: &mut HashMap<String, f64>) -> f64 {
+    /// Check that every monkey `key` ends up waiting on exists, and that none of them
+    /// end up waiting on themselves. `humn` is allowed to be missing, since it's us.
+    pub fn validate(&self, key: &str) -> Result<(), SolveError> {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum Visit {
+            InProgress,
+            Done,
+        }
 
-        if let Some(value) = memo.get(key) {
-            return *value;
-        } else {
+        let mut visits: HashMap<&str, Visit> = HashMap::new();
+        // Each name is pushed once on the way down, and once more on the way back up.
+        let mut stack = vec![(key, false)];
 
-            let Some(statement) = self.statements.get(key) else {
-                panic!("Unknown variable {}", key);
+        while let Some((name, finished)) = stack.pop() {
+            if finished {
+                visits.insert(name, Visit::Done);
+                continue;
+            }
+            match visits.get(name) {
+                Some(Visit::Done) => continue,
+                Some(Visit::InProgress) => return Err(SolveError::Cycle { node: name.to_string() }),
+                None => {},
+            }
+
+            let operation = match self.statements.get(name) {
+                None if name == HUMN => continue,
+                None => return Err(SolveError::Undefined(name.to_string())),
+                Some(Expression::Literal(_)) => None,
+                Some(Expression::Operation(op)) => Some(op),
             };
 
-            match statement {
-                Expression::Literal(v) => {
-                    // self.value_map.insert(key.to_string(), *v);
-                    *v
-                },
-                Expression::Operation(op) => {
-                    let left = self.evaluate_part1(&op.left, memo);
-                    let right = self.evaluate_part1(&op.right, memo);
-
-                    let result = match op.operator {
-                        Operator::Add => std::ops::Add::add(left, right),
-                        Operator::Subtract => std::ops::Sub::sub(left, right),
-                        Operator::Multiply => std::ops::Mul::mul(left, right),
-                        Operator::Divide => std::ops::Div::div(left, right),
-                    };
-
-                    memo.insert(key.to_string(), result);
-                    result
-                }
+            visits.insert(name, Visit::InProgress);
+            stack.push((name, true));
+            if let Some(op) = operation {
+                stack.push((op.right.as_str(), false));
+                stack.push((op.left.as_str(), false));
             }
         }
+        Ok(())
     }
 
+    /// The exact value of `key`, with `humn` taken at face value.
+    /// This recurses through the tree, so [`Tree::validate`] it first if it might have a cycle.
+    pub fn evaluate(&self, key: &str, memo: &mut HashMap<String, Rational>) -> Result<Rational, SolveError> {
+        if let Some(value) = memo.get(key) {
+            return Ok(*value);
+        }
+
+        let Some(statement) = self.statements.get(key) else {
+            return Err(SolveError::Undefined(key.to_string()));
+        };
+
+        let result = match statement {
+            Expression::Literal(v) => Rational::from(*v),
+            Expression::Operation(op) => {
+                let left = self.evaluate(&op.left, memo)?;
+                let right = self.evaluate(&op.right, memo)?;
+
+                if op.operator == Operator::Divide && right.is_zero() {
+                    return Err(SolveError::DivisionByZero { node: key.to_string() });
+                }
+                op.operator
+                    .apply(left, right)
+                    .ok_or_else(|| SolveError::Overflow { node: key.to_string() })?
+            }
+        };
 
-    pub fn part1(&self) -> i64 {
-        let mut memo = HashMap::new();
-        self.evaluate_part1("root", &mut memo);
-        memo.get("root").unwrap().round() as i64
+        memo.insert(key.to_string(), result);
+        Ok(result)
     }
 
-    /// Modify the tree slightly so that the root always subtracts its two subtrees and
-    /// the `humn` is a given literal value.
-    pub fn build_tree_for_part2(&self, x_coordinate: f64) -> Tree {
-        let mut stmts = self.statements.clone();
-        stmts
-        .entry("root".to_string())
-        .and_modify(
-            |e| {
-                match e {
-                    Expression::Operation(op) => {
-                        op.operator = Operator::Subtract;
-                    },
-                _ => {}
+    /// Whether the value of `key` depends on what `humn` yells.
+    pub fn depends_on_humn(&self, key: &str, memo: &mut HashMap<String, bool>) -> Result<bool, SolveError> {
+        if key == HUMN {
+            return Ok(true);
+        }
+        if let Some(depends) = memo.get(key) {
+            return Ok(*depends);
+        }
+
+        let depends = match self.statements.get(key) {
+            None => return Err(SolveError::Undefined(key.to_string())),
+            Some(Expression::Literal(_)) => false,
+            Some(Expression::Operation(op)) => {
+                // Both sides, so that an undefined name is reported either way.
+                let left = self.depends_on_humn(&op.left, memo)?;
+                let right = self.depends_on_humn(&op.right, memo)?;
+                left || right
             }
-        });
-        stmts
-        .entry("humn".to_string())
-        .and_modify(
-            |e| {
-                match e {
-                    Expression::Literal(_) => {
-                    *e = Expression::Literal(x_coordinate);
+        };
+
+        memo.insert(key.to_string(), depends);
+        Ok(depends)
+    }
+
+    /// Treat `root` as an equality and find the value of `humn` that makes it hold.
+    ///
+    /// Evaluate whichever side of `root` doesn't involve `humn`, then walk down
+    /// towards `humn`, undoing one operation at a time: if `a + humn` has to be `t`,
+    /// `humn` has to be `t - a`, and so on. Everything stays an exact [`Rational`].
+    ///
+    /// That only works if `humn` shows up in one operand of every operation on the way,
+    /// and never as a divisor, since `a / humn = t` isn't linear in `humn`.
+    pub fn solve_for_humn(&self) -> Result<Rational, SolveError> {
+        self.validate(ROOT)?;
+        let mut values = HashMap::new();
+        let mut depends = HashMap::new();
+
+        let mut key = ROOT.to_string();
+        let mut target: Option<Rational> = None;
+
+        while key != HUMN {
+            let op = match self.statements.get(&key) {
+                Some(Expression::Operation(op)) => op,
+                Some(Expression::Literal(_)) if key == ROOT => return Err(SolveError::MissingHumn),
+                // Only operations can depend on `humn`, and we only ever step into those.
+                Some(Expression::Literal(_)) => unreachable!("{key} does not depend on humn"),
+                None => return Err(SolveError::Undefined(key)),
+            };
+
+            let humn_on_left = self.depends_on_humn(&op.left, &mut depends)?;
+            let humn_on_right = self.depends_on_humn(&op.right, &mut depends)?;
+
+            let (next, other) = match (humn_on_left, humn_on_right) {
+                (true, true) => return Err(SolveError::HumnOnBothSides { node: key }),
+                (false, false) => return Err(SolveError::MissingHumn),
+                (true, false) => (&op.left, self.evaluate(&op.right, &mut values)?),
+                (false, true) => (&op.right, self.evaluate(&op.left, &mut values)?),
+            };
+
+            let undone = match target {
+                // `root` just says both sides are equal.
+                None => Some(other),
+                Some(target) => match (op.operator, humn_on_left) {
+                    (Operator::Add, _) => target.checked_sub(other),
+                    (Operator::Subtract, true) => target.checked_add(other),
+                    (Operator::Subtract, false) => other.checked_sub(target),
+                    (Operator::Multiply, _) if other.is_zero() => {
+                        return Err(SolveError::NoUniqueSolution { node: key })
+                    },
+                    (Operator::Multiply, _) => target.checked_div(other),
+                    (Operator::Divide, true) if other.is_zero() => {
+                        return Err(SolveError::DivisionByZero { node: key })
+                    },
+                    (Operator::Divide, true) => target.checked_mul(other),
+                    (Operator::Divide, false) => return Err(SolveError::Nonlinear { node: key }),
                 },
-                _ => {}
-            }
-        });
-        
-        Tree {
-            statements: stmts
+            };
+            target = Some(undone.ok_or_else(|| SolveError::Overflow { node: key.clone() })?);
+            key = next.clone();
         }
+
+        target.ok_or(SolveError::MissingHumn)
+    }
+
+    pub fn part1(&self) -> Result<i64, SolveError> {
+        self.validate(ROOT)?;
+        let value = self.evaluate(ROOT, &mut HashMap::new())?;
+        whole(ROOT, value)
     }
 
-    /// Idea:
-    /// 
-    /// We're assuming there's only one reference of `humn` in the tree so that
-    /// arithmetic evaluation ends up being a linear equation in `humn`.
-    /// A quick inspection of the dataset shows that this is the case.
-    /// 
-    /// Now we can try to find the zero of the function:
-    /// f(humn) = value(left_child given `humn`) - value(right_child given `humn`)
-    /// 
-    /// Since this is a linear function, we can model it as y = mx + b for some gradient m 
-    /// and intercept b. We can then solve for the value of `humn` that makes the function
-    /// zero by rearranging the equation to:
-    /// humn = -b / m
-    /// 
-    /// To calculate the gradient, we choose two points on the function and calculate the ratio of
-    /// the difference in y values to the difference in x values. We have to be careful to choose large
-    /// enough values for x so that the difference in y values is significant and the gradients rounds off to
-    /// a nice whole number.
-    pub fn part2(&self) -> i64 {
-
-        // Just choose a wide enough step size to get a good enough answer.
-        let x2 = 1000.0;
-        let x1 = 0.0;
-
-        let tree_at_x2 = self.build_tree_for_part2(x2);
-        let tree_at_x1 = self.build_tree_for_part2(x1);
-
-        let mut memo_x1 = HashMap::new();
-        tree_at_x1.evaluate_part1("root", &mut memo_x1);
-        let y1 = *memo_x1.get("root").unwrap();
-
-        let mut memo_x2 = HashMap::new();
-        tree_at_x2.evaluate_part1("root", &mut memo_x2);
-        let y2 = *memo_x2.get("root").unwrap();
-
-        let gradient = (y2 - y1) / (x2 - x1);
-        let intercept = y1;
-
-        (-intercept / gradient).round() as i64
+    pub fn part2(&self) -> Result<i64, SolveError> {
+        whole(HUMN, self.solve_for_humn()?)
     }
 
 }
 
 
+pub const ROOT: &str = "root";
+pub const HUMN: &str = "humn";
+
+
+/// What `node` yells, as the whole number the puzzle asks for.
+fn whole(node: &str, value: Rational) -> Result<i64, SolveError> {
+    let value = value.to_integer().ok_or(SolveError::Fractional { node: node.to_string(), value })?;
+    i64::try_from(value).map_err(|_| SolveError::Overflow { node: node.to_string() })
+}
+
+
+/// Why there's no single value for `humn` that satisfies `root`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// A monkey waits on a name nobody yells.
+    Undefined(String),
+    /// `root` doesn't depend on `humn` at all.
+    MissingHumn,
+    /// Both operands of `node` depend on `humn`, so it can't be undone on its own.
+    HumnOnBothSides { node: String },
+    /// `humn` ends up in the divisor at `node`.
+    Nonlinear { node: String },
+    /// `node` multiplies `humn` by zero, so any value (or none) would do.
+    NoUniqueSolution { node: String },
+    DivisionByZero { node: String },
+    /// `node` ends up waiting on itself.
+    Cycle { node: String },
+    /// The value at `node` doesn't fit in the integers we keep it in.
+    Overflow { node: String },
+    /// `node` comes out as a fraction rather than a whole number.
+    Fractional { node: String, value: Rational },
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Undefined(name) => write!(f, "Unknown variable {}", name),
+            SolveError::MissingHumn => write!(f, "{} does not depend on {}", ROOT, HUMN),
+            SolveError::HumnOnBothSides { node } => {
+                write!(f, "{} appears on both sides of {}", HUMN, node)
+            },
+            SolveError::Nonlinear { node } => {
+                write!(f, "{} divides by an expression in {}, which is not linear", node, HUMN)
+            },
+            SolveError::NoUniqueSolution { node } => {
+                write!(f, "{} multiplies {} by zero", node, HUMN)
+            },
+            SolveError::DivisionByZero { node } => write!(f, "{} divides by zero", node),
+            SolveError::Cycle { node } => write!(f, "{} ends up waiting on itself", node),
+            SolveError::Overflow { node } => write!(f, "{} overflows", node),
+            SolveError::Fractional { node, value } => write!(f, "{} is {}, not a whole number", node, value),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+
 pub mod parse {
     use super::*;
     use nom::branch::alt;
@@ -210,8 +365,8 @@ pub mod parse {
 
     pub fn expression(s: &str) -> IResult<&str, Expression> {
         alt((
-            map(operation, |op| Expression::Operation(op)),
-            map(parse_i64, |v| Expression::Literal(v as f64))
+            map(operation, Expression::Operation),
+            map(parse_i64, Expression::Literal)
         ))(s)
     }
 
@@ -272,14 +427,14 @@ pub mod parse {
                 left: "a".to_string(),
                 right: "b".to_string()
             }))));
-            assert_eq!(expression("123"), Ok(("", Expression::Literal(123.0))));
+            assert_eq!(expression("123"), Ok(("", Expression::Literal(123))));
         }
 
         #[test]
         fn test_statement() {
             assert_eq!(statement("a: 123"), Ok(("", Statement {
                 target: "a".to_string(),
-                value: Expression::Literal(123.0)
+                value: Expression::Literal(123)
             })));
             assert_eq!(statement("ccza: ac + qb"), Ok(("", Statement {
                 target: "ccza".to_string(),
@@ -294,6 +449,25 @@ pub mod parse {
     }
 }
 
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Tree;
+    type Answer1 = Result<i64, SolveError>;
+    type Answer2 = Result<i64, SolveError>;
+
+    fn parse(&self, input: &str) -> Self::Input {
+        parse::statements(input).unwrap().1.into()
+    }
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        input.part1()
+    }
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        input.part2()
+    }
+}
+
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -320,8 +494,91 @@ hmdt: 32";
         let stmts = statements(input).unwrap().1;
         let tree: Tree = stmts.into();
 
-        assert_eq!(tree.part1(), 152);
-        assert_eq!(tree.part2(), 301);
+        assert_eq!(tree.part1(), Ok(152));
+        assert_eq!(tree.part2(), Ok(301));
+
+    }
+
+    fn tree(input: &str) -> Tree {
+        statements(input).unwrap().1.into()
+    }
+
+    #[test]
+    fn solves_exactly_through_fractions() {
+        // (humn / 3) * 2 = 10 only works out if the division isn't rounded.
+        let thirds = tree("root: half + ten
+half: third * two
+third: humn / three
+humn: 0
+two: 2
+three: 3
+ten: 10");
+        assert_eq!(thirds.solve_for_humn(), Ok(Rational::from(15)));
+
+        // And answers that aren't whole numbers come out as fractions.
+        let halves = tree("root: double + seven
+double: two * humn
+humn: 0
+two: 2
+seven: 7");
+        assert_eq!(halves.solve_for_humn(), Ok(Rational::new(7, 2)));
+        assert_eq!(
+            halves.part2(),
+            Err(SolveError::Fractional { node: HUMN.to_string(), value: Rational::new(7, 2) })
+        );
+    }
+
+    #[test]
+    fn reports_overflow() {
+        let squares = "big: 9000000000000000000
+square: big * big
+cube: square * big
+one: 1";
+        let overflows = tree(&format!("root: cube + one\nhumn: 0\n{}", squares));
+        assert_eq!(overflows.part1(), Err(SolveError::Overflow { node: "cube".to_string() }));
+
+        let too_big = tree(&format!("root: square + one\nhumn: 0\n{}", squares));
+        assert_eq!(too_big.part1(), Err(SolveError::Overflow { node: ROOT.to_string() }));
+
+        let undone = tree(&format!("root: scaled + square\nscaled: humn / big\nhumn: 0\n{}", squares));
+        assert_eq!(undone.part2(), Err(SolveError::Overflow { node: "scaled".to_string() }));
+    }
+
+    #[test]
+    fn reports_what_it_cannot_invert() {
+        let both_sides = tree("root: twice + one
+twice: humn + humn
+humn: 0
+one: 1");
+        assert_eq!(both_sides.solve_for_humn(), Err(SolveError::HumnOnBothSides { node: "twice".to_string() }));
+
+        let divisor = tree("root: inverse + one
+inverse: one / humn
+humn: 0
+one: 1");
+        assert_eq!(divisor.solve_for_humn(), Err(SolveError::Nonlinear { node: "inverse".to_string() }));
+
+        let zero = tree("root: nothing + one
+nothing: humn * zero
+humn: 0
+zero: 0
+one: 1");
+        assert_eq!(zero.solve_for_humn(), Err(SolveError::NoUniqueSolution { node: "nothing".to_string() }));
+
+        let missing = tree("root: one + one
+humn: 0
+one: 1");
+        assert_eq!(missing.solve_for_humn(), Err(SolveError::MissingHumn));
+
+        let undefined = tree("root: humn + nobody
+humn: 0");
+        assert_eq!(undefined.solve_for_humn(), Err(SolveError::Undefined("nobody".to_string())));
+    }
 
+    #[test]
+    fn real_input() {
+        let monkeys = tree(include_str!("input.txt").trim_end());
+        assert_eq!(monkeys.part1(), Ok(82225382988628));
+        assert_eq!(monkeys.part2(), Ok(3429411069028));
     }
 }
\ No newline at end of file
@@ -1,10 +1,14 @@
+use advent::InputLoader;
+use std::error::Error;
 use day_21::{*, parse::statements};
 
-pub fn main() {
-    let input = include_str!("input.txt");
-    let stmts = statements(input).unwrap().1;
+fn main() -> Result<(), Box<dyn Error>> {
+    let input = InputLoader::from_args(21).load()?;
+    let stmts = statements(&input).unwrap().1;
     let tree: Tree = stmts.into();
     
-    println!("Part 1: {}", tree.part1());
-    println!("Part 2: {}", tree.part2());
-}
\ No newline at end of file
+    println!("Part 1: {}", tree.part1()?);
+    println!("Part 2: {}", tree.part2()?);
+
+    Ok(())
+}
@@ -0,0 +1,157 @@
+use std::fmt::Display;
+use std::ops::Neg;
+
+
+/// An exact fraction, always kept in lowest terms with a positive denominator
+/// so that equal values compare equal.
+///
+/// The arithmetic is all `checked_*`, since the numbers in a long enough chain
+/// of monkeys outgrow an `i128` long before they stop being exact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { numerator: 0, denominator: 1 };
+
+    /// Panics if `denominator` is zero, or if the fraction in lowest terms
+    /// still has `i128::MIN` in it.
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert!(denominator != 0, "Rational with a zero denominator.");
+        Self::reduced(numerator, denominator).expect("Rational does not fit in lowest terms.")
+    }
+
+    /// `None` if `denominator` is zero or the fraction doesn't fit once it's in lowest terms.
+    /// Keeping `i128::MIN` out of the numerator means negating one never overflows.
+    fn reduced(numerator: i128, denominator: i128) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let negative = (numerator < 0) != (denominator < 0);
+        let divisor = gcd(numerator.unsigned_abs(), denominator.unsigned_abs());
+        let magnitude = i128::try_from(numerator.unsigned_abs() / divisor).ok()?;
+        let denominator = i128::try_from(denominator.unsigned_abs() / divisor).ok()?;
+
+        Some(Self {
+            numerator: if negative { -magnitude } else { magnitude },
+            denominator,
+        })
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    /// The value as a whole number, if it is one.
+    pub fn to_integer(&self) -> Option<i128> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+
+    /// `None` on overflow.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let left = self.numerator.checked_mul(rhs.denominator)?;
+        let right = rhs.numerator.checked_mul(self.denominator)?;
+        Self::reduced(left.checked_add(right)?, self.denominator.checked_mul(rhs.denominator)?)
+    }
+
+    /// `None` on overflow.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(-rhs)
+    }
+
+    /// `None` on overflow.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        Self::reduced(
+            self.numerator.checked_mul(rhs.numerator)?,
+            self.denominator.checked_mul(rhs.denominator)?,
+        )
+    }
+
+    /// `None` on overflow, or when dividing by zero.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        Self::reduced(
+            self.numerator.checked_mul(rhs.denominator)?,
+            self.denominator.checked_mul(rhs.numerator)?,
+        )
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Self { numerator: value as i128, denominator: 1 }
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Self::Output {
+        Rational { numerator: -self.numerator, denominator: self.denominator }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stays_in_lowest_terms() {
+        assert_eq!(Rational::new(6, -4), Rational::new(-3, 2));
+        assert_eq!(Rational::new(0, -7), Rational::ZERO);
+        assert_eq!(Rational::new(-6, -4).to_string(), "3/2");
+    }
+
+    #[test]
+    fn arithmetic_is_exact() {
+        let third = Rational::new(1, 3);
+        assert_eq!(third.checked_add(third).and_then(|sum| sum.checked_add(third)), Some(Rational::from(1)));
+        assert_eq!(Rational::from(1).checked_sub(third), Some(Rational::new(2, 3)));
+        assert_eq!(third.checked_mul(Rational::from(6)), Some(Rational::from(2)));
+        assert_eq!(Rational::from(7).checked_div(Rational::from(2)), Some(Rational::new(7, 2)));
+        assert_eq!(Rational::new(7, 2).to_integer(), None);
+        assert_eq!(Rational::from(8).checked_div(Rational::from(2)).and_then(|n| n.to_integer()), Some(4));
+    }
+
+    #[test]
+    fn division_by_zero_is_none() {
+        assert_eq!(Rational::from(1).checked_div(Rational::ZERO), None);
+    }
+
+    #[test]
+    fn overflow_is_none() {
+        let big = Rational::new(i128::MAX, 1);
+        assert_eq!(big.checked_add(Rational::from(1)), None);
+        assert_eq!(big.checked_mul(Rational::from(2)), None);
+        assert_eq!((-big).checked_sub(Rational::from(2)), None);
+        assert_eq!(Rational::new(1, i128::MAX).checked_div(big), None);
+        assert_eq!(big.checked_sub(Rational::from(1)), Some(Rational::new(i128::MAX - 1, 1)));
+    }
+}
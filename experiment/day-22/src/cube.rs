@@ -0,0 +1,283 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::{Board, Coordinate, Direction, Tile};
+
+
+/// A point or direction in 3D. The cube is folded up as the unit cube,
+/// so every face corner ends up in `{0, 1}^3`.
+type Vec3 = [i8; 3];
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn neg(a: Vec3) -> Vec3 {
+    [-a[0], -a[1], -a[2]]
+}
+
+
+/// One face of the cube: where it is on the board and where it ended up after folding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Face {
+    /// Top-left tile of the face on the board.
+    pub origin: Coordinate,
+    /// Where the top-left corner of the face is on the cube.
+    corner: Vec3,
+    /// Going right (resp. down) on the board moves along these on the cube.
+    right: Vec3,
+    down: Vec3,
+    /// Points from the face into the cube.
+    inward: Vec3,
+}
+
+impl Face {
+    /// The face that shares the edge in `direction`, as laid out flat on the board.
+    fn unfolded_neighbor(&self, direction: Direction, size: usize) -> (Option<Coordinate>, Face) {
+        let (row, col) = self.origin;
+        let Face { corner, right, down, inward, .. } = *self;
+
+        match direction {
+            Direction::Right => (
+                Some((row, col + size)),
+                Face { origin: (row, col + size), corner: add(corner, right), right: inward, down, inward: neg(right) },
+            ),
+            Direction::Down => (
+                Some((row + size, col)),
+                Face { origin: (row + size, col), corner: add(corner, down), right, down: inward, inward: neg(down) },
+            ),
+            Direction::Left => (
+                col.checked_sub(size).map(|col| (row, col)),
+                Face { origin: (row, col.wrapping_sub(size)), corner: add(corner, inward), right: neg(inward), down, inward: right },
+            ),
+            Direction::Up => (
+                row.checked_sub(size).map(|row| (row, col)),
+                Face { origin: (row.wrapping_sub(size), col), corner: add(corner, inward), right, down: neg(inward), inward: down },
+            ),
+        }
+    }
+
+    /// The corners at either end of an edge, ordered the same way
+    /// as the tiles along it (top to bottom, or left to right).
+    fn edge(&self, direction: Direction) -> (Vec3, Vec3) {
+        let top_left = self.corner;
+        let top_right = add(self.corner, self.right);
+        let bottom_left = add(self.corner, self.down);
+        let bottom_right = add(bottom_left, self.right);
+
+        match direction {
+            Direction::Up => (top_left, top_right),
+            Direction::Down => (bottom_left, bottom_right),
+            Direction::Left => (top_left, bottom_left),
+            Direction::Right => (top_right, bottom_right),
+        }
+    }
+
+    fn outward(&self, direction: Direction) -> Vec3 {
+        match direction {
+            Direction::Up => neg(self.down),
+            Direction::Down => self.down,
+            Direction::Left => neg(self.right),
+            Direction::Right => self.right,
+        }
+    }
+}
+
+
+/// The board folded up into a cube, so that walking off a face
+/// carries on along whichever face is next to it on the cube.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CubeNet {
+    pub size: usize,
+    pub faces: Vec<Face>,
+}
+
+impl CubeNet {
+    /// Find the six faces on the board and fold them up.
+    /// Works for any of the 11 nets of a cube, and gives `None` for anything that isn't one.
+    pub fn fold(board: &Board) -> Option<Self> {
+        let tiles = board.board.iter().flatten().filter(|&&tile| tile != Tile::Blank).count();
+        let size = ((tiles / 6) as f64).sqrt().round() as usize;
+        if size == 0 || size * size * 6 != tiles {
+            return None;
+        }
+
+        let is_face = |(row, col): Coordinate| {
+            row < board.height() && col < board.width() && board.board[row][col] != Tile::Blank
+        };
+        let start = (0..board.width()).step_by(size).map(|col| (0, col)).find(|&origin| is_face(origin))?;
+
+        let mut faces: HashMap<Coordinate, Face> = HashMap::new();
+        let mut queue = VecDeque::from([Face {
+            origin: start,
+            corner: [0, 0, 0],
+            right: [1, 0, 0],
+            down: [0, 1, 0],
+            inward: [0, 0, 1],
+        }]);
+
+        while let Some(face) = queue.pop_front() {
+            if faces.contains_key(&face.origin) {
+                continue;
+            }
+            faces.insert(face.origin, face);
+
+            for direction in [Direction::Up, Direction::Down, Direction::Left, Direction::Right] {
+                if let (Some(origin), neighbor) = face.unfolded_neighbor(direction, size) {
+                    if is_face(origin) && !faces.contains_key(&origin) {
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+
+        let mut faces = faces.into_values().collect::<Vec<_>>();
+        faces.sort_by_key(|face| face.origin);
+
+        // Six faces that don't overlap once folded.
+        let mut inwards = faces.iter().map(|face| face.inward).collect::<Vec<_>>();
+        inwards.sort();
+        inwards.dedup();
+        if faces.len() != 6 || inwards.len() != 6 {
+            return None;
+        }
+
+        Some(Self { size, faces })
+    }
+
+    pub fn face(&self, (row, col): Coordinate) -> Option<&Face> {
+        self.faces.iter().find(|face| {
+            (face.origin.0..face.origin.0 + self.size).contains(&row)
+                && (face.origin.1..face.origin.1 + self.size).contains(&col)
+        })
+    }
+
+    /// Take one step from `position`, which has to be on one of the faces.
+    /// The direction changes if that step goes over an edge of the cube.
+    pub fn shift(&self, position: Coordinate, direction: Direction) -> (Coordinate, Direction) {
+        let face = self.face(position).expect("Position is not on the cube.");
+        let size = self.size as isize;
+        let (row, col) = (
+            (position.0 - face.origin.0) as isize,
+            (position.1 - face.origin.1) as isize,
+        );
+        let (delta_row, delta_col) = direction.delta();
+        let (next_row, next_col) = (row + delta_row, col + delta_col);
+
+        if (0..size).contains(&next_row) && (0..size).contains(&next_col) {
+            let next = (face.origin.0 + next_row as usize, face.origin.1 + next_col as usize);
+            return (next, direction);
+        }
+
+        // Over the edge, onto the face on the other side of it.
+        let along = match direction {
+            Direction::Left | Direction::Right => row as usize,
+            Direction::Up | Direction::Down => col as usize,
+        };
+        let (from, to) = face.edge(direction);
+        let outward = face.outward(direction);
+        let target = self.faces.iter().find(|other| other.inward == neg(outward)).unwrap();
+
+        let (entered_through, along) = [Direction::Up, Direction::Down, Direction::Left, Direction::Right]
+            .into_iter()
+            .find_map(|edge| match target.edge(edge) {
+                corners if corners == (from, to) => Some((edge, along)),
+                corners if corners == (to, from) => Some((edge, self.size - 1 - along)),
+                _ => None,
+            })
+            .unwrap();
+
+        let last = self.size - 1;
+        let (row, col) = match entered_through {
+            Direction::Up => (0, along),
+            Direction::Down => (last, along),
+            Direction::Left => (along, 0),
+            Direction::Right => (along, last),
+        };
+        let direction = entered_through.clockwise().clockwise();
+
+        ((target.origin.0 + row, target.origin.1 + col), direction)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_board;
+    use test_case::test_case;
+
+    /// Every net, with each `#` blown up into a 3x3 face.
+    fn board(net: &[&str]) -> Board {
+        let rows = net
+            .iter()
+            .flat_map(|row| {
+                let line = row
+                    .chars()
+                    .map(|c| if c == '#' { "..." } else { "   " })
+                    .collect::<String>();
+                vec![line.clone(), line.clone(), line]
+            })
+            .collect::<Vec<_>>();
+        parse_board(&rows.join("\n"))
+    }
+
+    #[test_case(&["#...", "####", "#..."]; "1-4-1 a")]
+    #[test_case(&["#...", "####", ".#.."]; "1-4-1 b")]
+    #[test_case(&["#...", "####", "..#."]; "1-4-1 c")]
+    #[test_case(&["#...", "####", "...#"]; "1-4-1 d")]
+    #[test_case(&[".#..", "####", ".#.."]; "1-4-1 e")]
+    #[test_case(&[".#..", "####", "..#."]; "1-4-1 f")]
+    #[test_case(&["##..", ".###", ".#.."]; "2-3-1 a")]
+    #[test_case(&["##..", ".###", "..#."]; "2-3-1 b")]
+    #[test_case(&["##..", ".###", "...#"]; "2-3-1 c")]
+    #[test_case(&["##..", ".##.", "..##"]; "2-2-2")]
+    #[test_case(&["###..", "..###"]; "3-3")]
+    fn folds_every_net(net: &[&str]) {
+        let board = board(net);
+        let cube = CubeNet::fold(&board).unwrap();
+        assert_eq!(cube.size, 3);
+        assert_eq!(cube.faces.len(), 6);
+        assert_walks_around(&board, &cube);
+    }
+
+    #[test]
+    fn folds_real_board() {
+        let game = crate::build_game(include_str!("input.txt"));
+        let cube = CubeNet::fold(&game.board).unwrap();
+        assert_eq!(cube.size, 50);
+        assert_walks_around(&game.board, &cube);
+    }
+
+    fn assert_walks_around(board: &Board, cube: &CubeNet) {
+        for face in &cube.faces {
+            for row in face.origin.0..face.origin.0 + cube.size {
+                for col in face.origin.1..face.origin.1 + cube.size {
+                    for direction in [Direction::Up, Direction::Down, Direction::Left, Direction::Right] {
+                        // Walking over an edge and turning around has to bring us back.
+                        let (next, next_direction) = cube.shift((row, col), direction);
+                        assert_ne!(board.board[next.0][next.1], Tile::Blank);
+
+                        let back = next_direction.clockwise().clockwise();
+                        let (prev, prev_direction) = cube.shift(next, back);
+                        assert_eq!(prev, (row, col));
+                        assert_eq!(prev_direction, direction.clockwise().clockwise());
+
+                        // So does going all the way around the cube.
+                        let (mut position, mut heading) = ((row, col), direction);
+                        for _ in 0..4 * cube.size {
+                            (position, heading) = cube.shift(position, heading);
+                        }
+                        assert_eq!((position, heading), ((row, col), direction));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_what_is_not_a_net() {
+        assert_eq!(CubeNet::fold(&board(&["####", "####"])), None);
+        assert_eq!(CubeNet::fold(&board(&["######"])), None);
+        assert_eq!(CubeNet::fold(&board(&["##..", "##..", "##.."])), None);
+    }
+}
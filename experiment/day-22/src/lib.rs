@@ -1,22 +1,32 @@
+mod cube;
 mod parse;
+pub use cube::*;
 pub use parse::*;
+use advent::Solution;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Tile {
     Open,
     Occupied,
+    #[default]
     Blank
 }
 
-impl Default for Tile {
-    fn default() -> Self {
-        Self::Blank
-    }
-}
 
+/// The map, with every row padded out with blank tiles to the width of the widest one.
 #[derive(Debug, Clone)]
-pub struct Board<const N: usize, const M: usize> {
-    pub board: [[Tile; N]; M]
+pub struct Board {
+    pub board: Vec<Vec<Tile>>
+}
+
+impl Board {
+    pub fn width(&self) -> usize {
+        self.board.first().map_or(0, Vec::len)
+    }
+
+    pub fn height(&self) -> usize {
+        self.board.len()
+    }
 }
 
 
@@ -34,17 +44,12 @@ pub struct Instructions {
 }
 
 
-// #[derive(Debug, Clone)]
-// pub struct Game<const N: usize, const M: usize> {
-//     pub board: Board<N, M>,
-//     pub instructions: Instructions,
-// }
-
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub enum Direction {
     Up,
     Down,
     Left,
+    #[default]
     Right,
 }
 
@@ -85,43 +90,53 @@ impl Direction {
     }
 }
 
-impl Default for Direction {
-    fn default() -> Self {
-        Self::Right
-    }
-}
 
 
 pub type Coordinate = (usize, usize);
 
 
+/// What happens when we walk off the edge of the map.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Wrapping {
+    /// Come back in on the opposite side of the same row or column.
+    #[default]
+    Flat,
+    /// Carry on along the adjacent face of the folded up cube.
+    Cube(CubeNet),
+}
+
+
 #[derive(Debug, Clone)]
-pub struct Game<const N: usize, const M: usize> {
-    pub board: Board<N, M>,
+pub struct Game {
+    pub board: Board,
     pub instructions: Instructions,
     pub current_position: Coordinate,
     pub current_direction: Direction,
+    pub wrapping: Wrapping,
 }
 
 
-impl<const N: usize, const M: usize> Game<N, M> {
-    pub fn new(board: Board<N, M>, instructions: Instructions) -> Self {
+impl Game {
+    pub fn new(board: Board, instructions: Instructions) -> Self {
 
-        let (index, _) = board.board[0].iter().enumerate().find(|&c| {
-            match c.1 {
-                Tile::Open => true,
-                _ => false
-            }
-        }).expect("No open cell on the first row.");
+        let (index, _) = board.board[0].iter().enumerate().find(|&c| matches!(c.1, Tile::Open)).expect("No open cell on the first row.");
 
         Self {
             board,
             instructions,
             current_position: (0, index),
-            current_direction: Direction::default()
+            current_direction: Direction::default(),
+            wrapping: Wrapping::default(),
         }
     }
 
+    /// Switch to walking around the board folded up as a cube.
+    /// Panics if the board isn't the net of a cube.
+    pub fn fold_into_cube(&mut self) {
+        let cube = CubeNet::fold(&self.board).expect("The board is not the net of a cube.");
+        self.wrapping = Wrapping::Cube(cube);
+    }
+
     #[inline(always)]
     pub fn wrapping_shift(&self, index: Coordinate, direction: Direction) -> Coordinate {
         let delta = direction.delta();
@@ -131,15 +146,17 @@ impl<const N: usize, const M: usize> Game<N, M> {
         let mut updated_row = row as isize + delta.0;
         let mut updated_col = col as isize + delta.1;
 
+        let (width, height) = (self.board.width() as isize, self.board.height() as isize);
+
         if updated_row == -1 {
-            updated_row = M as isize - 1;
-        } else if updated_row == M as isize {
+            updated_row = height - 1;
+        } else if updated_row == height {
             updated_row = 0;
         }
 
         if updated_col == -1 {
-            updated_col = N as isize - 1;
-        } else if updated_col == N as isize {
+            updated_col = width - 1;
+        } else if updated_col == width {
             updated_col = 0;
         }
 
@@ -148,14 +165,24 @@ impl<const N: usize, const M: usize> Game<N, M> {
 
 
     pub fn step(&mut self, step: &Step) {
-        match step {
-            Step::Clockwise => {
+        match (step, &self.wrapping) {
+            (Step::Clockwise, _) => {
                 self.current_direction = self.current_direction.clockwise();
             },
-            Step::CounterClockwise => {
+            (Step::CounterClockwise, _) => {
                 self.current_direction = self.current_direction.counter_clockwise();
             },
-            Step::Forward(units) => {
+            (Step::Forward(units), Wrapping::Cube(cube)) => {
+                for _ in 0..*units {
+                    let (next_position, next_direction) = cube.shift(self.current_position, self.current_direction);
+                    if self.board.board[next_position.0][next_position.1] == Tile::Occupied {
+                        break;
+                    }
+                    self.current_position = next_position;
+                    self.current_direction = next_direction;
+                }
+            },
+            (Step::Forward(units), Wrapping::Flat) => {
                 if *units == 0 {
                     return;
                 }
@@ -199,4 +226,83 @@ impl<const N: usize, const M: usize> Game<N, M> {
         ((self.current_position.0 + 1, self.current_position.1 + 1), self.current_direction)
     }
 
-}
\ No newline at end of file
+}
+
+
+pub fn build_game(s: &str) -> Game {
+    let mut board_and_instructions = s.split("\n\n");
+    let board_map = board_and_instructions.next().unwrap();
+    let instructions = board_and_instructions.next().unwrap();
+    let instructions = parse_instructions(instructions).unwrap().1;
+    let board = parse_board(board_map);
+
+    Game::new(board, instructions)
+}
+
+pub fn solve_part1(game: &mut Game) -> usize {
+    let ((final_row, final_col), final_direction) = game.play();
+    final_row * 1000 + final_col * 4 + final_direction.score()
+}
+
+
+pub fn solve_part2(game: &mut Game) -> usize {
+    game.fold_into_cube();
+    solve_part1(game)
+}
+
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Game;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Self::Input {
+        build_game(input)
+    }
+    fn part1(&self, input: &Self::Input) -> Self::Answer1 {
+        solve_part1(&mut input.clone())
+    }
+    fn part2(&self, input: &Self::Input) -> Self::Answer2 {
+        solve_part2(&mut input.clone())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_works() {
+        let input = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5";
+
+        let mut game = build_game(input);
+        let score = solve_part1(&mut game.clone());
+        assert_eq!(score, 6032);
+
+        let score = solve_part2(&mut game);
+        assert_eq!(score, 5031);
+    }
+
+    #[test]
+    fn real_board_folds() {
+        let game = build_game(include_str!("input.txt"));
+        assert_eq!(solve_part1(&mut game.clone()), 122220);
+        assert_eq!(solve_part2(&mut game.clone()), 34426);
+    }
+}
@@ -9,11 +9,9 @@ use nom::{
 
 use crate::{Board, Instructions, Step, Tile};
 
-// N = 150
-// M = 200
-
-pub fn parse_board<const N: usize, const M: usize>(s: &str) -> Board<N, M> {
-    let mut board = [[Tile::default(); N]; M];
+pub fn parse_board(s: &str) -> Board {
+    let width = s.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+    let mut board = vec![vec![Tile::default(); width]; s.lines().count()];
 
     for (y, line) in s.lines().enumerate() {
         for (x, c) in line.chars().enumerate() {